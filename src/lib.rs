@@ -16,6 +16,21 @@ pub enum ProcErr {
     IO(std::io::Error),
     Parse(Box<dyn std::error::Error>),
     BadFormat(String),
+    /// The address is not mapped in the target process while accessing /proc/[pid]/mem.
+    Unmapped(usize),
+    /// The file or feature is not supported by the running kernel.
+    Unsupported(String),
+}
+
+impl ProcErr {
+    /// Return true if the error is caused by lacking of permission,
+    /// e.g. ptrace access mode check failed or the file is only readable by root.
+    pub fn is_permission_denied(&self) -> bool {
+        match self {
+            ProcErr::IO(e) => e.kind() == std::io::ErrorKind::PermissionDenied,
+            _ => false,
+        }
+    }
 }
 
 impl From<std::io::Error> for ProcErr {
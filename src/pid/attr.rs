@@ -0,0 +1,208 @@
+// /proc/[pid]/attr
+// The files in this directory provide an API for security mod‐
+// ules.  The contents of this directory are files that can be
+// read and written in order to set security-related attributes.
+// This directory was added to support SELinux, but the intention
+// was that the API be general enough to support other security
+// modules.  For the purpose of explanation, examples of how
+// SELinux uses these files are provided below.
+//
+// This directory is present only if the kernel was configured
+// with CONFIG_SECURITY.
+//
+// /proc/[pid]/attr/current (since Linux 2.6.0)
+// The contents of this file represent the current security
+// attributes of the process.
+//
+// In SELinux, this file is used to get the security context of a
+// process.  Prior to Linux 2.6.11, this file could not be used to
+// set the security context (a write was always denied), since
+// SELinux limited process security transitions to execve(2) (see
+// the description of /proc/[pid]/attr/exec, below).  Since Linux
+// 2.6.11, SELinux lifted this restriction and began supporting
+// "set" operations via writes to this node if authorized by pol‐
+// icy, although use of this operation is only suitable for
+// applications that are trusted to maintain any desired separa‐
+// tion between the old and new security contexts.
+//
+// /proc/[pid]/attr/exec (since Linux 2.6.0)
+// The attributes assigned to the process upon a subsequent
+// execve(2).
+//
+// /proc/[pid]/attr/prev (since Linux 2.6.0)
+// This file contains the security context of the process before
+// the last execve(2); that is, the previous value of
+// /proc/[pid]/attr/current.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// Since Linux 5.1, each security module may also provide its own attributes in
+// /proc/[pid]/attr/<lsm>/, such as /proc/[pid]/attr/apparmor/current and
+// /proc/[pid]/attr/smack/current, which does not depend on which module owns
+// the top level files. SELinux only provides the top level files.
+
+use crate::pid::Pid;
+
+/// The security module whose attributes are read.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Lsm {
+    /// The top level files in /proc/[pid]/attr, owned by the first major security module.
+    Default,
+    /// The top level files in /proc/[pid]/attr, same as `Default` since SELinux
+    /// has no directory of its own. The labels belong to another module if SELinux is
+    /// not the first major security module.
+    SELinux,
+    AppArmor,
+    Smack,
+}
+
+impl Lsm {
+    fn dir(self) -> &'static str {
+        match self {
+            Lsm::Default | Lsm::SELinux => "",
+            Lsm::AppArmor => "apparmor/",
+            Lsm::Smack => "smack/",
+        }
+    }
+}
+
+define_struct! {
+    /// Represent the security labels in /proc/[pid]/attr, returned by [`attr_of()`](fn.attr_of.html).
+    ///
+    /// A label is None if the file is empty or not supported by the security module,
+    /// or the directory of the security module is absent.
+    pub struct Attr {
+        current: Option<String>,
+        exec: Option<String>,
+        prev: Option<String>,
+    }
+}
+
+fn read_label(path: &str) -> Result<Option<String>, crate::ProcErr> {
+    const EINVAL: i32 = 22;
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let label = content.trim_end_matches(['\0', '\n']);
+            if label.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(label.to_string()))
+            }
+        }
+        // no module handles the top level files.
+        Err(ref e) if e.raw_os_error() == Some(EINVAL) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn read_lsm_label(dir: &str, lsm: Lsm, name: &str) -> Result<Option<String>, crate::ProcErr> {
+    match read_label(&format!("{}/attr/{}{}", dir, lsm.dir(), name)) {
+        // the directory of the module is absent if it is not enabled,
+        // but the attr directory is gone too if the process has exited.
+        Err(crate::ProcErr::IO(ref e))
+            if e.kind() == std::io::ErrorKind::NotFound
+                && !lsm.dir().is_empty()
+                && std::path::Path::new(&format!("{}/attr", dir)).exists() =>
+        {
+            Ok(None)
+        }
+        label => label,
+    }
+}
+
+fn attr_impl(dir: &str, lsm: Lsm) -> Result<Attr, crate::ProcErr> {
+    let current = read_lsm_label(dir, lsm, "current")?;
+    let exec = read_lsm_label(dir, lsm, "exec")?;
+    let prev = read_lsm_label(dir, lsm, "prev")?;
+    Ok(Attr {
+        current,
+        exec,
+        prev,
+    })
+}
+
+/// Return the security labels of process `pid` provided by `lsm`.
+pub fn attr_of(pid: Pid, lsm: Lsm) -> Result<Attr, crate::ProcErr> {
+    attr_impl(&format!("/proc/{}", pid), lsm)
+}
+
+/// Return the security labels of current process provided by `lsm`.
+pub fn attr_self(lsm: Lsm) -> Result<Attr, crate::ProcErr> {
+    attr_impl("/proc/self", lsm)
+}
+
+define_struct! {
+    /// Who launched a process and under which security label, returned by [`launch_info_of()`](fn.launch_info_of.html).
+    pub struct LaunchInfo {
+        pid: i32,
+        ppid: i32,
+        comm: String,
+        /// The audit login uid, None if it is not set or the kernel is built without CONFIG_AUDIT.
+        loginuid: Option<u32>,
+        /// The audit session id, None if it is not set or the kernel is built without CONFIG_AUDIT.
+        sessionid: Option<u32>,
+        /// The current label in /proc/[pid]/attr/current,
+        /// None if the kernel is built without CONFIG_SECURITY.
+        label: Option<String>,
+    }
+}
+
+// loginuid and sessionid only exist with CONFIG_AUDIT.
+fn audit_id(id: Result<Option<u32>, crate::ProcErr>) -> Result<Option<u32>, crate::ProcErr> {
+    match id {
+        Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        id => id,
+    }
+}
+
+/// Collect [`LaunchInfo`](struct.LaunchInfo.html) of process `pid` from
+/// /proc/[pid]/stat, /proc/[pid]/loginuid, /proc/[pid]/sessionid and /proc/[pid]/attr/current.
+pub fn launch_info_of(pid: Pid) -> Result<LaunchInfo, crate::ProcErr> {
+    let stat = crate::pid::stat::stat_of(pid)?;
+    let loginuid = audit_id(crate::pid::loginuid::loginuid_of(pid).map(|l| l.into_inner()))?;
+    let sessionid = audit_id(crate::pid::sessionid::sessionid_of(pid).map(|s| s.into_inner()))?;
+    let label = match read_label(&format!("/proc/{}/attr/current", pid)) {
+        // the attr directory only exists with CONFIG_SECURITY.
+        Err(crate::ProcErr::IO(ref e))
+            if e.kind() == std::io::ErrorKind::NotFound
+                && std::path::Path::new(&format!("/proc/{}", pid)).exists()
+                && !std::path::Path::new(&format!("/proc/{}/attr", pid)).exists() =>
+        {
+            None
+        }
+        label => label?,
+    };
+    Ok(LaunchInfo {
+        pid: *stat.pid(),
+        ppid: *stat.ppid(),
+        comm: stat.comm().to_string(),
+        loginuid,
+        sessionid,
+        label,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_attr_self() {
+        let attr = attr_self(Lsm::Default).unwrap();
+        assert_eq!(attr, attr_self(Lsm::SELinux).unwrap());
+        let attr = attr_self(Lsm::Smack).unwrap();
+        assert_eq!(None, attr.prev);
+
+        match attr_of(Pid::MAX, Lsm::Smack) {
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_launch_info() {
+        let pid = std::process::id();
+        let info = launch_info_of(pid).unwrap();
+        assert_eq!(pid as i32, info.pid);
+    }
+}
@@ -0,0 +1,218 @@
+// /proc/[pid]/auxv (since 2.6.0)
+// This contains the contents of the ELF interpreter information
+// passed to the process at exec time.  The format is one
+// unsigned long ID plus one unsigned long value for each entry.
+// The last entry contains two zeros.  See also getauxval(3).
+//
+// Permission to access this file is governed by a ptrace access
+// mode PTRACE_MODE_READ_FSCREDS check; see ptrace(2).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// The values of AT_PLATFORM, AT_BASE_PLATFORM, AT_RANDOM and AT_EXECFN are
+// addresses in the address space of the process, not the data itself.
+//
+// Reference to [`include/uapi/linux/auxvec.h`](https://github.com/torvalds/linux/blob/master/include/uapi/linux/auxvec.h)
+
+use crate::pid::{Pid, Tid};
+
+/// The key of an auxiliary vector entry.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AuxvKey {
+    Ignore,
+    ExecFd,
+    Phdr,
+    Phent,
+    Phnum,
+    PageSz,
+    Base,
+    Flags,
+    Entry,
+    NotElf,
+    Uid,
+    Euid,
+    Gid,
+    Egid,
+    Platform,
+    HwCap,
+    ClkTck,
+    Secure,
+    BasePlatform,
+    Random,
+    HwCap2,
+    RseqFeatureSize,
+    RseqAlign,
+    HwCap3,
+    HwCap4,
+    ExecFn,
+    Sysinfo,
+    SysinfoEhdr,
+    MinSigStkSz,
+    Unknown(usize),
+}
+
+impl From<usize> for AuxvKey {
+    fn from(n: usize) -> AuxvKey {
+        match n {
+            1 => AuxvKey::Ignore,
+            2 => AuxvKey::ExecFd,
+            3 => AuxvKey::Phdr,
+            4 => AuxvKey::Phent,
+            5 => AuxvKey::Phnum,
+            6 => AuxvKey::PageSz,
+            7 => AuxvKey::Base,
+            8 => AuxvKey::Flags,
+            9 => AuxvKey::Entry,
+            10 => AuxvKey::NotElf,
+            11 => AuxvKey::Uid,
+            12 => AuxvKey::Euid,
+            13 => AuxvKey::Gid,
+            14 => AuxvKey::Egid,
+            15 => AuxvKey::Platform,
+            16 => AuxvKey::HwCap,
+            17 => AuxvKey::ClkTck,
+            23 => AuxvKey::Secure,
+            24 => AuxvKey::BasePlatform,
+            25 => AuxvKey::Random,
+            26 => AuxvKey::HwCap2,
+            27 => AuxvKey::RseqFeatureSize,
+            28 => AuxvKey::RseqAlign,
+            29 => AuxvKey::HwCap3,
+            30 => AuxvKey::HwCap4,
+            31 => AuxvKey::ExecFn,
+            32 => AuxvKey::Sysinfo,
+            33 => AuxvKey::SysinfoEhdr,
+            51 => AuxvKey::MinSigStkSz,
+            x => AuxvKey::Unknown(x),
+        }
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/auxv, returned by [`auxv_of()`](fn.auxv_of.html).
+    ///
+    /// The terminating AT_NULL entry is not included.
+    pub struct Auxv(Vec<(AuxvKey, usize)>);
+}
+
+impl Auxv {
+    /// Parse the auxiliary vector from raw bytes in native word size and endian.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Auxv, crate::ProcErr> {
+        const WORD: usize = std::mem::size_of::<usize>();
+        let chunks = bytes.chunks_exact(WORD * 2);
+        if !chunks.remainder().is_empty() {
+            return Err("auxv length is not a multiple of entry size".into());
+        }
+
+        let mut entries = vec![];
+        for entry in chunks {
+            let mut key = [0; WORD];
+            let mut value = [0; WORD];
+            key.copy_from_slice(&entry[..WORD]);
+            value.copy_from_slice(&entry[WORD..]);
+            let key = usize::from_ne_bytes(key);
+            let value = usize::from_ne_bytes(value);
+            if key == 0 {
+                break;
+            }
+            entries.push((AuxvKey::from(key), value));
+        }
+        Ok(Auxv(entries))
+    }
+
+    /// Return the value of the first entry with `key`.
+    pub fn get(&self, key: AuxvKey) -> Option<usize> {
+        self.0.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    /// Return the system page size (AT_PAGESZ).
+    pub fn page_size(&self) -> Option<usize> {
+        self.get(AuxvKey::PageSz)
+    }
+
+    /// Return the frequency of times() (AT_CLKTCK), which is the unit of
+    /// time fields in [`Stat`](../stat/struct.Stat.html).
+    pub fn clock_ticks(&self) -> Option<usize> {
+        self.get(AuxvKey::ClkTck)
+    }
+}
+
+fn auxv_impl(path: &str) -> Result<Auxv, crate::ProcErr> {
+    let bytes = std::fs::read(path)?;
+    Auxv::from_bytes(&bytes)
+}
+
+/// Return parsed content of /proc/[pid]/auxv.
+pub fn auxv_of(pid: Pid) -> Result<Auxv, crate::ProcErr> {
+    auxv_impl(&format!("/proc/{}/auxv", pid))
+}
+
+/// Return parsed content of /proc/self/auxv.
+pub fn auxv_self() -> Result<Auxv, crate::ProcErr> {
+    auxv_impl("/proc/self/auxv")
+}
+
+/// Return parsed content of /proc/[pid]/task/[tid]/auxv.
+pub fn auxv_of_of(pid: Pid, tid: Tid) -> Result<Auxv, crate::ProcErr> {
+    auxv_impl(&format!("/proc/{}/task/{}/auxv", pid, tid))
+}
+
+/// Return parsed content of /proc/self/task/[tid]/auxv.
+pub fn auxv_self_of(tid: Tid) -> Result<Auxv, crate::ProcErr> {
+    auxv_impl(&format!("/proc/self/task/{}/auxv", tid))
+}
+
+/// Return parsed content of /proc/thread-self/auxv.
+pub fn auxv_self_self() -> Result<Auxv, crate::ProcErr> {
+    auxv_impl("/proc/thread-self/auxv")
+}
+
+/// Return the system page size in bytes, read from /proc/self/auxv.
+///
+/// The page counts in [`Statm`](../statm/struct.Statm.html) are measured in this unit.
+pub fn page_size() -> Result<usize, crate::ProcErr> {
+    auxv_self()?
+        .page_size()
+        .ok_or_else(|| "AT_PAGESZ not found in auxv".into())
+}
+
+/// Return the number of clock ticks per second, read from /proc/self/auxv.
+///
+/// The time fields in [`Stat`](../stat/struct.Stat.html) are measured in this unit.
+pub fn clock_ticks() -> Result<usize, crate::ProcErr> {
+    auxv_self()?
+        .clock_ticks()
+        .ok_or_else(|| "AT_CLKTCK not found in auxv".into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_auxv() {
+        let mut bytes = vec![];
+        for n in [6, 4096, 17, 100, 99, 1, 0, 0].iter() {
+            bytes.extend_from_slice(&(*n as usize).to_ne_bytes());
+        }
+        let correct = Auxv(vec![
+            (AuxvKey::PageSz, 4096),
+            (AuxvKey::ClkTck, 100),
+            (AuxvKey::Unknown(99), 1),
+        ]);
+        let auxv = Auxv::from_bytes(&bytes).unwrap();
+        assert_eq!(correct, auxv);
+        assert_eq!(Some(4096), auxv.page_size());
+        assert_eq!(Some(100), auxv.clock_ticks());
+        assert_eq!(None, auxv.get(AuxvKey::HwCap));
+
+        assert!(Auxv::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_auxv_self() {
+        let auxv = auxv_self().unwrap();
+        assert!(auxv.page_size().is_some());
+        assert!(auxv.clock_ticks().is_some());
+    }
+}
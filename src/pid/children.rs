@@ -0,0 +1,315 @@
+// /proc/[pid]/task/[tid]/children (since Linux 3.5)
+// A space-separated list of child tasks of this task.  Each
+// child task is represented by its TID.
+//
+// This option is intended for use by the checkpoint-restore
+// (CRIU) system, and reliably provides a list of children only if
+// all of the child processes are stopped or frozen.  It does not
+// work properly if children of the target task exit while the
+// file is being read!  Exiting children may cause non-exiting
+// children to be omitted from the list.  This makes this inter‐
+// face even more unreliable than classic PID-based approaches if
+// the inspected task and its children aren't frozen, and most
+// code should probably not use this interface.
+//
+// Until Linux 4.2, the presence of this file was governed by the
+// CONFIG_CHECKPOINT_RESTORE kernel configuration option.  Since
+// Linux 4.2, it is governed by the CONFIG_PROC_CHILDREN option.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::pid::{Pid, Tid};
+use std::collections::BTreeMap;
+
+fn children_impl(path: &str) -> Result<Vec<Pid>, crate::ProcErr> {
+    let content = std::fs::read_to_string(path)?;
+    let mut ret = vec![];
+    for child in content.split_ascii_whitespace() {
+        ret.push(child.parse::<Pid>()?);
+    }
+    Ok(ret)
+}
+
+/// Return the children of thread `tid` in process `pid`.
+pub fn children_of_of(pid: Pid, tid: Tid) -> Result<Vec<Pid>, crate::ProcErr> {
+    children_impl(&format!("/proc/{}/task/{}/children", pid, tid))
+}
+
+/// Return the children of thread `tid` in current process.
+pub fn children_self_of(tid: Tid) -> Result<Vec<Pid>, crate::ProcErr> {
+    children_impl(&format!("/proc/self/task/{}/children", tid))
+}
+
+/// Return the children of all threads in process `pid`, sorted by pid.
+pub fn children_of(pid: Pid) -> Result<Vec<Pid>, crate::ProcErr> {
+    let mut ret = vec![];
+    for tid in crate::pid::task::task_of(pid)? {
+        match children_of_of(pid, tid) {
+            Ok(children) => ret.extend(children),
+            // the thread exited after listing.
+            Err(crate::ProcErr::IO(ref e))
+                if e.kind() == std::io::ErrorKind::NotFound && tid != pid => {}
+            Err(e) => return Err(e),
+        }
+    }
+    ret.sort_unstable();
+    ret.dedup();
+    Ok(ret)
+}
+
+/// Return the children of all threads in current process, sorted by pid.
+pub fn children_self() -> Result<Vec<Pid>, crate::ProcErr> {
+    children_of(std::process::id())
+}
+
+define_struct! {
+    /// A process in [`ProcessTree`](struct.ProcessTree.html).
+    pub struct ProcessNode {
+        pid: Pid,
+        ppid: Pid,
+        comm: String,
+        /// Sorted by pid.
+        children: Vec<Pid>,
+    }
+}
+
+define_struct! {
+    /// The parent/child graph of processes, returned by [`process_tree()`](fn.process_tree.html).
+    ///
+    /// A process whose parent is not in the tree is a root,
+    /// e.g. init and kthreadd whose ppid is 0.
+    pub struct ProcessTree {
+        nodes: BTreeMap<Pid, ProcessNode>,
+        roots: Vec<Pid>,
+    }
+}
+
+impl ProcessTree {
+    /// Build a tree from (pid, ppid, comm) of each process,
+    /// children are derived from ppid.
+    pub fn from_entries<I>(entries: I) -> ProcessTree
+    where
+        I: IntoIterator<Item = (Pid, Pid, String)>,
+    {
+        let mut nodes: BTreeMap<Pid, ProcessNode> = entries
+            .into_iter()
+            .map(|(pid, ppid, comm)| {
+                let node = ProcessNode {
+                    pid,
+                    ppid,
+                    comm,
+                    children: vec![],
+                };
+                (pid, node)
+            })
+            .collect();
+
+        let mut roots = vec![];
+        let links: Vec<(Pid, Pid)> = nodes.values().map(|n| (n.pid, n.ppid)).collect();
+        for (pid, ppid) in links {
+            match nodes.get_mut(&ppid) {
+                Some(parent) if ppid != pid => parent.children.push(pid),
+                _ => roots.push(pid),
+            }
+        }
+
+        ProcessTree { nodes, roots }
+    }
+
+    /// Return the process `pid`.
+    pub fn get(&self, pid: Pid) -> Option<&ProcessNode> {
+        self.nodes.get(&pid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Iterate all processes in depth-first order, each item is the depth and the process.
+    /// Roots have depth 0.
+    pub fn iter(&self) -> Iter<'_> {
+        let stack = self.roots.iter().rev().map(|p| (0, *p)).collect();
+        Iter { tree: self, stack }
+    }
+
+    /// Return the tree rooted at process `pid`, None if `pid` is not in the tree.
+    pub fn subtree(&self, pid: Pid) -> Option<ProcessTree> {
+        let root = self.nodes.get(&pid)?;
+        let mut nodes = BTreeMap::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            stack.extend(node.children.iter().filter_map(|c| self.nodes.get(c)));
+            nodes.insert(node.pid, node.clone());
+        }
+        Some(ProcessTree {
+            nodes,
+            roots: vec![pid],
+        })
+    }
+
+    /// Return the parent, grandparent, ... of process `pid` up to a root.
+    /// Return an empty vector if `pid` is a root or is not in the tree.
+    pub fn ancestors(&self, pid: Pid) -> Vec<Pid> {
+        let mut ret = vec![];
+        let mut current = pid;
+        while let Some(node) = self.nodes.get(&current) {
+            if !self.nodes.contains_key(&node.ppid) || node.ppid == node.pid {
+                break;
+            }
+            // guard against a loop built by pid reuse while reading /proc.
+            if ret.contains(&node.ppid) || node.ppid == pid {
+                break;
+            }
+            ret.push(node.ppid);
+            current = node.ppid;
+        }
+        ret
+    }
+
+    /// Render the tree like pstree(1), for example:
+    ///
+    /// ```text
+    /// systemd(1)
+    /// ├─sshd(812)
+    /// │ └─bash(901)
+    /// └─cron(815)
+    /// ```
+    pub fn render(&self) -> String {
+        let mut ret = String::new();
+        for root in self.roots.iter() {
+            self.render_node(*root, "", None, &mut ret);
+        }
+        ret
+    }
+
+    fn render_node(&self, pid: Pid, prefix: &str, last: Option<bool>, out: &mut String) {
+        let node = match self.nodes.get(&pid) {
+            Some(node) => node,
+            None => return,
+        };
+        let child_prefix = match last {
+            None => {
+                out.push_str(&format!("{}({})\n", node.comm, node.pid));
+                String::new()
+            }
+            Some(last) => {
+                let branch = if last { "└─" } else { "├─" };
+                out.push_str(&format!(
+                    "{}{}{}({})\n",
+                    prefix, branch, node.comm, node.pid
+                ));
+                format!("{}{}", prefix, if last { "  " } else { "│ " })
+            }
+        };
+        for (i, child) in node.children.iter().enumerate() {
+            let is_last = i + 1 == node.children.len();
+            self.render_node(*child, &child_prefix, Some(is_last), out);
+        }
+    }
+}
+
+impl std::fmt::Display for ProcessTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Depth-first iterator over a [`ProcessTree`](struct.ProcessTree.html), returned by `ProcessTree::iter()`.
+pub struct Iter<'a> {
+    tree: &'a ProcessTree,
+    stack: Vec<(usize, Pid)>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (usize, &'a ProcessNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, pid)) = self.stack.pop() {
+            if let Some(node) = self.tree.nodes.get(&pid) {
+                self.stack
+                    .extend(node.children.iter().rev().map(|c| (depth + 1, *c)));
+                return Some((depth, node));
+            }
+        }
+        None
+    }
+}
+
+/// Build the [`ProcessTree`](struct.ProcessTree.html) of all processes.
+///
+/// Pid, ppid and comm are read from /proc/[pid]/stat and children are derived from ppid,
+/// /proc/[pid]/task/[tid]/children is not used since it may omit live children.
+/// Processes exiting while the tree is built are skipped.
+pub fn process_tree() -> Result<ProcessTree, crate::ProcErr> {
+    let mut entries = vec![];
+    for pid in crate::pid::pids()? {
+        match crate::pid::stat::stat_of(pid) {
+            Ok(stat) => entries.push((pid, *stat.ppid() as Pid, stat.comm().to_string())),
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(ProcessTree::from_entries(entries))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> ProcessTree {
+        ProcessTree::from_entries(vec![
+            (1, 0, "systemd".to_string()),
+            (2, 0, "kthreadd".to_string()),
+            (812, 1, "sshd".to_string()),
+            (815, 1, "cron".to_string()),
+            (901, 812, "bash".to_string()),
+            (950, 901, "vim".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_process_tree() {
+        let tree = sample();
+        assert_eq!(6, tree.len());
+        assert_eq!(&vec![1, 2], tree.roots());
+        assert_eq!(&vec![812, 815], tree.get(1).unwrap().children());
+
+        let order: Vec<(usize, Pid)> = tree.iter().map(|(d, n)| (d, n.pid)).collect();
+        assert_eq!(
+            vec![(0, 1), (1, 812), (2, 901), (3, 950), (1, 815), (0, 2)],
+            order
+        );
+
+        assert_eq!(vec![901, 812, 1], tree.ancestors(950));
+        assert!(tree.ancestors(1).is_empty());
+
+        let sub = tree.subtree(812).unwrap();
+        assert_eq!(3, sub.len());
+        assert_eq!(&vec![812], sub.roots());
+        assert!(tree.subtree(4242).is_none());
+    }
+
+    #[test]
+    fn test_render() {
+        let expect = "systemd(1)
+├─sshd(812)
+│ └─bash(901)
+│   └─vim(950)
+└─cron(815)
+kthreadd(2)
+";
+        assert_eq!(expect, sample().render());
+    }
+
+    #[test]
+    fn test_process_tree_self() {
+        let tree = process_tree().unwrap();
+        let pid = std::process::id();
+        let ppid = *tree.get(pid).unwrap().ppid();
+        assert!(tree.get(ppid).unwrap().children().contains(&pid));
+    }
+}
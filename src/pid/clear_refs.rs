@@ -0,0 +1,214 @@
+// /proc/[pid]/clear_refs (since Linux 2.6.22)
+//
+// This is a write-only file, writable only by owner of the
+// process.
+//
+// The following values may be written to the file:
+//
+// 1 (since Linux 2.6.22)
+//        Reset the PG_Referenced and ACCESSED/YOUNG bits for
+//        all the pages associated with the process.  (Before
+//        kernel 2.6.32, writing any nonzero value to this file
+//        had this effect.)
+//
+// 2 (since Linux 2.6.32)
+//        Reset the PG_Referenced and ACCESSED/YOUNG bits for
+//        all anonymous pages associated with the process.
+//
+// 3 (since Linux 2.6.32)
+//        Reset the PG_Referenced and ACCESSED/YOUNG bits for
+//        all file-mapped pages associated with the process.
+//
+// Clearing the PG_Referenced and ACCESSED/YOUNG bits provides a
+// method to measure approximately how much memory a process is
+// using.  One first inspects the values in the "Referenced"
+// fields for the VMAs shown in /proc/[pid]/smaps to get an idea
+// of the memory footprint of the process.  One then clears the
+// PG_Referenced and ACCESSED/YOUNG bits and, after some measured
+// time interval, once again inspects the values in the "Refer‐
+// enced" fields to get an idea of the change in memory footprint
+// of the process during the measured interval.  If one is inter‐
+// ested only in inspecting the selected mapping types, then the
+// value 2 or 3 can be used instead of 1.
+//
+// Further values can be written to affect different properties:
+//
+// 4 (since Linux 3.11)
+//        Clear the soft-dirty bit for all the pages associated
+//        with the process.  This is used (in conjunction with
+//        /proc/[pid]/pagemap) by the check-point restore system
+//        to discover which pages of a process have been dirtied
+//        since the file /proc/[pid]/clear_refs was written to.
+//
+// 5 (since Linux 4.0)
+//        Reset the peak resident set size ("high water mark")
+//        to the process's current resident set size value.
+//
+// Writing any value to /proc/[pid]/clear_refs other than those
+// listed above has no effect.
+//
+// The /proc/[pid]/clear_refs file is present only if the CON‐
+// FIG_PROC_PAGE_MONITOR kernel configuration option is enabled.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::pid::Pid;
+
+/// The value written to /proc/[pid]/clear_refs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ClearRefs {
+    /// Reset the referenced bits of all pages.
+    All = 1,
+    /// Reset the referenced bits of anonymous pages.
+    Anonymous = 2,
+    /// Reset the referenced bits of file-mapped pages.
+    File = 3,
+    /// Clear the soft-dirty bits of all pages.
+    SoftDirty = 4,
+    /// Reset the peak resident set size to current resident set size.
+    PeakRss = 5,
+}
+
+/// Write `mode` to /proc/[pid]/clear_refs.
+pub fn clear_refs_of(pid: Pid, mode: ClearRefs) -> Result<(), crate::ProcErr> {
+    std::fs::write(
+        format!("/proc/{}/clear_refs", pid),
+        (mode as u8).to_string(),
+    )?;
+    Ok(())
+}
+
+/// Write `mode` to /proc/self/clear_refs.
+pub fn clear_refs_self(mode: ClearRefs) -> Result<(), crate::ProcErr> {
+    std::fs::write("/proc/self/clear_refs", (mode as u8).to_string())?;
+    Ok(())
+}
+
+/// Return the number of pages whose soft-dirty bit is set in process `pid`,
+/// that is pages written since last [`ClearRefs::SoftDirty`](enum.ClearRefs.html#variant.SoftDirty).
+/// Only pages in RAM or swap space are counted.
+///
+/// The working set of a process over a time window can be estimated by clearing soft-dirty bits
+/// at the beginning of the window, and calling this function at the end of it.
+/// Multiply the result by [`page_size()`](../auxv/fn.page_size.html) to get it in bytes.
+///
+/// The soft-dirty bits are always cleared if the kernel is built without CONFIG_MEM_SOFT_DIRTY.
+pub fn soft_dirty_pages_of(pid: Pid) -> Result<usize, crate::ProcErr> {
+    soft_dirty_pages_impl(
+        crate::pid::maps::maps_of(pid)?,
+        &format!("/proc/{}/pagemap", pid),
+    )
+}
+
+/// Return the number of pages whose soft-dirty bit is set in current process.
+///
+/// See [`soft_dirty_pages_of()`](fn.soft_dirty_pages_of.html) for details.
+pub fn soft_dirty_pages_self() -> Result<usize, crate::ProcErr> {
+    soft_dirty_pages_impl(crate::pid::maps::maps_self()?, "/proc/self/pagemap")
+}
+
+fn soft_dirty_pages_impl(
+    maps: crate::pid::maps::Maps,
+    path: &str,
+) -> Result<usize, crate::ProcErr> {
+    let mut count = 0;
+    for map in maps.iter() {
+        let ret = crate::pid::pagemap::pagemap_chunks(path, *map.address(), |entries| {
+            // the bit is also set on unmapped pages of a mapping created after the clear.
+            count += entries
+                .iter()
+                .filter(|e| (e.present() || e.swapped()) && e.soft_dirty())
+                .count();
+            Ok(())
+        });
+        match ret {
+            Ok(()) => {}
+            // regions out of user space like [vsyscall] are not reported by pagemap.
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(count)
+}
+
+/// Tracks the working set of a process over a time window using soft-dirty bits.
+///
+/// ```no_run
+/// use lpfs::pid::clear_refs::WorkingSet;
+/// let ws = WorkingSet::start(1234).unwrap();
+/// std::thread::sleep(std::time::Duration::from_secs(60));
+/// let pages = ws.pages().unwrap();
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorkingSet {
+    pid: Pid,
+    started: std::time::Instant,
+}
+
+impl WorkingSet {
+    /// Clear the soft-dirty bits of process `pid` and start a new time window.
+    pub fn start(pid: Pid) -> Result<WorkingSet, crate::ProcErr> {
+        clear_refs_of(pid, ClearRefs::SoftDirty)?;
+        Ok(WorkingSet {
+            pid,
+            started: std::time::Instant::now(),
+        })
+    }
+
+    /// Return the time elapsed since the window started.
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.started.elapsed()
+    }
+
+    /// Return the number of pages written since the window started.
+    pub fn pages(&self) -> Result<usize, crate::ProcErr> {
+        soft_dirty_pages_of(self.pid)
+    }
+
+    /// Return the number of bytes written since the window started.
+    pub fn bytes(&self) -> Result<usize, crate::ProcErr> {
+        Ok(self.pages()? * crate::pid::auxv::page_size()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a single test since clearing the soft-dirty bits affects the whole process.
+    #[test]
+    fn test_clear_soft_dirty() {
+        // page aligned, so no other allocation shares the pages.
+        let page_size = crate::pid::auxv::page_size().unwrap();
+        let layout = std::alloc::Layout::from_size_align(page_size * 64, page_size).unwrap();
+        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+        assert!(!ptr.is_null());
+        let range = (ptr as usize, ptr as usize + layout.size());
+        let write = |value: u8| {
+            for offset in (0..layout.size()).step_by(page_size) {
+                unsafe { std::ptr::write_volatile(ptr.add(offset), value) };
+            }
+        };
+
+        write(1);
+        clear_refs_self(ClearRefs::SoftDirty).unwrap();
+        let entries = crate::pid::pagemap::pagemap_self(range).unwrap();
+        assert!(entries.iter().all(|e| e.present() && !e.soft_dirty()));
+
+        let ws = WorkingSet::start(std::process::id()).unwrap();
+        write(2);
+        let entries = crate::pid::pagemap::pagemap_self(range).unwrap();
+        let dirty = entries.iter().filter(|e| e.soft_dirty()).count();
+        let pages = soft_dirty_pages_self();
+        let ws_pages = ws.pages();
+        unsafe { std::alloc::dealloc(ptr, layout) };
+
+        // the bit is never set without CONFIG_MEM_SOFT_DIRTY.
+        if dirty == 0 {
+            return;
+        }
+        assert_eq!(entries.len(), dirty);
+        assert!(pages.unwrap() >= dirty);
+        assert!(ws_pages.unwrap() >= dirty);
+    }
+}
@@ -0,0 +1,162 @@
+// /proc/[pid]/coredump_filter (since Linux 2.6.23)
+// When a process is dumped, all anonymous memory is written to a core file as
+// long as the size of the core file isn't limited. But sometimes we don't want
+// to dump some memory segments, for example, huge shared memory or DAX.
+// Conversely, sometimes we want to save file-backed memory segments into a core
+// file, not only the individual files.
+//
+// /proc/<pid>/coredump_filter allows you to customize which memory segments
+// will be dumped when the <pid> process is dumped. coredump_filter is a bitmask
+// of memory types. If a bit of the bitmask is set, memory segments of the
+// corresponding memory type are dumped, otherwise they are not dumped.
+//
+// The following 9 memory types are supported:
+//   - (bit 0) anonymous private memory
+//   - (bit 1) anonymous shared memory
+//   - (bit 2) file-backed private memory
+//   - (bit 3) file-backed shared memory
+//   - (bit 4) ELF header pages in file-backed private memory areas (it is
+//     effective only if the bit 2 is cleared)
+//   - (bit 5) hugetlb private memory
+//   - (bit 6) hugetlb shared memory
+//   - (bit 7) DAX private memory
+//   - (bit 8) DAX shared memory
+//
+// Note that MMIO pages such as frame buffer are never dumped and vDSO pages
+// are always dumped regardless of the bitmask status.
+//
+// Note that bits 0-4 don't affect hugetlb or DAX memory. hugetlb memory is
+// only affected by bit 5-6, and DAX is only affected by bits 7-8.
+//
+// The default value of coredump_filter is 0x33; this means all anonymous memory
+// segments, ELF header pages and hugetlb private memory are dumped.
+//
+// If you don't want to dump all shared memory segments attached to pid 1234,
+// write 0x31 to the process's proc file:
+//
+//     $ echo 0x31 > /proc/1234/coredump_filter
+//
+// When a new process is created, the process inherits the bitmask status from
+// its parent. It is useful to set up coredump_filter before the program runs.
+//
+// -- https://www.kernel.org/doc/Documentation/filesystems/proc.rst
+
+use crate::pid::Pid;
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/coredump_filter, returned by [`coredump_filter_of()`](fn.coredump_filter_of.html).
+    pub struct CoredumpFilter(u32);
+}
+
+impl From<u32> for CoredumpFilter {
+    fn from(bits: u32) -> CoredumpFilter {
+        CoredumpFilter(bits)
+    }
+}
+
+impl Default for CoredumpFilter {
+    /// The kernel default 0x33.
+    fn default() -> CoredumpFilter {
+        CoredumpFilter(0x33)
+    }
+}
+
+macro_rules! flag_methods {
+    ($(
+        $method: ident, $set_method: ident, $bit: expr
+    );*) => {
+        impl CoredumpFilter {$(
+            #[doc="Return true if bit "]
+            #[doc=stringify!($bit)]
+            #[doc=" is set."]
+            pub fn $method(&self) -> bool {
+                self.0 & (1 << $bit) != 0
+            }
+
+            #[doc="Set or clear bit "]
+            #[doc=stringify!($bit)]
+            #[doc="."]
+            pub fn $set_method(&mut self, value: bool) -> &mut CoredumpFilter {
+                if value {
+                    self.0 |= 1 << $bit;
+                } else {
+                    self.0 &= !(1 << $bit);
+                }
+                self
+            }
+        )*}
+    };
+}
+
+flag_methods! {
+    anon_private, set_anon_private, 0;
+    anon_shared, set_anon_shared, 1;
+    file_private, set_file_private, 2;
+    file_shared, set_file_shared, 3;
+    elf_headers, set_elf_headers, 4;
+    hugetlb_private, set_hugetlb_private, 5;
+    hugetlb_shared, set_hugetlb_shared, 6;
+    dax_private, set_dax_private, 7;
+    dax_shared, set_dax_shared, 8
+}
+
+use std::str::FromStr;
+impl FromStr for CoredumpFilter {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<CoredumpFilter, crate::ProcErr> {
+        let bits = u32::from_str_radix(s.trim(), 16)?;
+        Ok(CoredumpFilter(bits))
+    }
+}
+
+pid_instance_impl! {
+    coredump_filter_of, "coredump_filter", CoredumpFilter,
+    coredump_filter_self, coredump_filter_of_of, coredump_filter_self_of, coredump_filter_self_self
+}
+
+/// Write /proc/[pid]/coredump_filter, children created after this inherit the filter.
+pub fn set_coredump_filter_of(pid: Pid, filter: CoredumpFilter) -> Result<(), crate::ProcErr> {
+    std::fs::write(
+        format!("/proc/{}/coredump_filter", pid),
+        format!("{:#x}", filter.0),
+    )?;
+    Ok(())
+}
+
+/// Write /proc/self/coredump_filter, children created after this inherit the filter.
+pub fn set_coredump_filter_self(filter: CoredumpFilter) -> Result<(), crate::ProcErr> {
+    std::fs::write("/proc/self/coredump_filter", format!("{:#x}", filter.0))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_coredump_filter() {
+        let filter = "00000033".parse::<CoredumpFilter>().unwrap();
+        assert_eq!(CoredumpFilter::default(), filter);
+        assert!(filter.anon_private());
+        assert!(filter.anon_shared());
+        assert!(!filter.file_private());
+        assert!(filter.elf_headers());
+        assert!(filter.hugetlb_private());
+        assert!(!filter.dax_shared());
+    }
+
+    #[test]
+    fn test_set_flags() {
+        let mut filter = CoredumpFilter::default();
+        filter.set_anon_shared(false).set_dax_shared(true);
+        assert_eq!(CoredumpFilter(0x131), filter);
+    }
+
+    #[test]
+    fn test_write_coredump_filter() {
+        let origin = coredump_filter_self().unwrap();
+        set_coredump_filter_self(origin.clone()).unwrap();
+        assert_eq!(origin, coredump_filter_self().unwrap());
+    }
+}
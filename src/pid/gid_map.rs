@@ -0,0 +1,24 @@
+// /proc/[pid]/gid_map (since Linux 3.5)
+// See user_namespaces(7).
+//
+// The format is the same as /proc/[pid]/uid_map, see [`uid_map`](../uid_map/index.html).
+//
+// Writing to gid_map requires writing "deny" to /proc/[pid]/setgroups first for
+// unprivileged processes, see [`setgroups`](../setgroups/index.html).
+
+use crate::pid::uid_map::write_id_map;
+pub use crate::pid::uid_map::{IdMap, IdRange};
+use crate::pid::Pid;
+
+pid_instance_impl! {
+    gid_map_of, "gid_map", IdMap,
+    gid_map_self, gid_map_of_of, gid_map_self_of, gid_map_self_self
+}
+
+/// Write the group id mapping of process `pid`, which can only be done once for a user namespace.
+///
+/// An unprivileged writer must write "deny" to /proc/[pid]/setgroups before this,
+/// see [`write_setgroups_of()`](../setgroups/fn.write_setgroups_of.html).
+pub fn write_gid_map_of(pid: Pid, map: &IdMap) -> Result<(), crate::ProcErr> {
+    write_id_map(&format!("/proc/{}/gid_map", pid), map)
+}
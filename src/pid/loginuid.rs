@@ -0,0 +1,45 @@
+// /proc/[pid]/loginuid
+//
+// This file holds the audit login uid of the process, which is set by pam_loginuid
+// when the user logs in and is inherited by all of its descendants.
+// 4294967295 ((uid_t)-1) means the login uid is not set,
+// e.g. processes started during boot.
+//
+// This file is present only if the kernel was configured with CONFIG_AUDIT.
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/loginuid, returned by [`loginuid_of()`](fn.loginuid_of.html).
+    ///
+    /// None means the login uid is not set.
+    pub struct LoginUid(Option<u32>);
+}
+
+use std::str::FromStr;
+impl FromStr for LoginUid {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<LoginUid, crate::ProcErr> {
+        let uid = s.trim().parse::<u32>()?;
+        if uid == u32::MAX {
+            Ok(LoginUid(None))
+        } else {
+            Ok(LoginUid(Some(uid)))
+        }
+    }
+}
+
+pid_instance_impl! {
+    loginuid_of, "loginuid", LoginUid,
+    loginuid_self, loginuid_of_of, loginuid_self_of, loginuid_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_loginuid() {
+        assert_eq!(LoginUid(None), "4294967295".parse::<LoginUid>().unwrap());
+        assert_eq!(LoginUid(Some(1000)), "1000".parse::<LoginUid>().unwrap());
+    }
+}
@@ -5,3 +5,140 @@
 // mode PTRACE_MODE_ATTACH_FSCREDS check; see ptrace(2).
 //
 // -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::pid::maps::Map;
+use crate::pid::Pid;
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::FileExt;
+
+/// An opened /proc/[pid]/mem, returned by [`mem_of()`](fn.mem_of.html) and [`mem_rw_of()`](fn.mem_rw_of.html).
+///
+/// Opening the file requires ptrace permission to the target process,
+/// [`ProcErr::is_permission_denied()`](../../enum.ProcErr.html#method.is_permission_denied)
+/// is true if it is denied.
+/// Accessing an address which is not mapped in the target process returns
+/// [`ProcErr::Unmapped`](../../enum.ProcErr.html#variant.Unmapped) with that address.
+#[derive(Debug)]
+pub struct ProcessMemory {
+    file: File,
+}
+
+impl ProcessMemory {
+    /// Read exactly `buf.len()` bytes start from `address`.
+    pub fn read_exact(&self, address: usize, buf: &mut [u8]) -> Result<(), crate::ProcErr> {
+        let mut done = 0;
+        while done < buf.len() {
+            let current = address + done;
+            match self.file.read_at(&mut buf[done..], current as u64) {
+                Ok(0) => return Err(crate::ProcErr::Unmapped(current)),
+                Ok(n) => done += n,
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(access_error(e, current)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Read `len` bytes start from `address`.
+    pub fn read(&self, address: usize, len: usize) -> Result<Vec<u8>, crate::ProcErr> {
+        let mut buf = vec![0; len];
+        self.read_exact(address, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Read the whole region of a mapping, see [`maps_of()`](../maps/fn.maps_of.html).
+    pub fn read_map(&self, map: &Map) -> Result<Vec<u8>, crate::ProcErr> {
+        let (start, end) = *map.address();
+        self.read(start, end - start)
+    }
+
+    /// Write all of `data` start from `address`.
+    ///
+    /// This requires the memory is opened by [`mem_rw_of()`](fn.mem_rw_of.html) or [`mem_rw_self()`](fn.mem_rw_self.html).
+    pub fn write(&self, address: usize, data: &[u8]) -> Result<(), crate::ProcErr> {
+        let mut done = 0;
+        while done < data.len() {
+            let current = address + done;
+            match self.file.write_at(&data[done..], current as u64) {
+                Ok(0) => return Err(crate::ProcErr::Unmapped(current)),
+                Ok(n) => done += n,
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(access_error(e, current)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The kernel returns EIO when accessing an unmapped address.
+fn access_error(e: std::io::Error, address: usize) -> crate::ProcErr {
+    const EIO: i32 = 5;
+    if e.raw_os_error() == Some(EIO) {
+        crate::ProcErr::Unmapped(address)
+    } else {
+        crate::ProcErr::IO(e)
+    }
+}
+
+fn open_mem(path: &str, writable: bool) -> Result<ProcessMemory, crate::ProcErr> {
+    let file = OpenOptions::new().read(true).write(writable).open(path)?;
+    Ok(ProcessMemory { file })
+}
+
+/// Open /proc/[pid]/mem for reading.
+pub fn mem_of(pid: Pid) -> Result<ProcessMemory, crate::ProcErr> {
+    open_mem(&format!("/proc/{}/mem", pid), false)
+}
+
+/// Open /proc/self/mem for reading.
+pub fn mem_self() -> Result<ProcessMemory, crate::ProcErr> {
+    open_mem("/proc/self/mem", false)
+}
+
+/// Open /proc/[pid]/mem for reading and writing.
+pub fn mem_rw_of(pid: Pid) -> Result<ProcessMemory, crate::ProcErr> {
+    open_mem(&format!("/proc/{}/mem", pid), true)
+}
+
+/// Open /proc/self/mem for reading and writing.
+pub fn mem_rw_self() -> Result<ProcessMemory, crate::ProcErr> {
+    open_mem("/proc/self/mem", true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mem_self() {
+        let data = vec![1u8, 2, 3, 4];
+        let address = data.as_ptr() as usize;
+
+        let mem = mem_rw_self().unwrap();
+        assert_eq!(data, mem.read(address, 4).unwrap());
+        mem.write(address + 1, &[9, 9]).unwrap();
+        assert_eq!(vec![1, 9, 9, 4], mem.read(address, 4).unwrap());
+    }
+
+    #[test]
+    fn test_mem_unmapped() {
+        let mem = mem_self().unwrap();
+        match mem.read(0, 1) {
+            Err(crate::ProcErr::Unmapped(0)) => (),
+            x => panic!("unexpected result: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_read_map() {
+        let data = [7u8; 16];
+        let address = data.as_ptr() as usize;
+        let source = format!(
+            "{:x}-{:x} rw-p 00000000 00:00 0",
+            address,
+            address + data.len()
+        );
+        let map = source.parse::<Map>().unwrap();
+        assert_eq!(data.to_vec(), mem_self().unwrap().read_map(&map).unwrap());
+    }
+}
@@ -7,15 +7,50 @@
 pub type Pid = u32;
 pub type Tid = u32;
 
+/// Return the process id of all processes, which are the numeric sub-directories of /proc.
+pub fn pids() -> Result<Vec<Pid>, crate::ProcErr> {
+    let mut ret = vec![];
+    for entry in std::fs::read_dir("/proc")? {
+        let file_name = entry?.file_name();
+        if let Some(pid) = file_name.to_str().and_then(|s| s.parse::<Pid>().ok()) {
+            ret.push(pid);
+        }
+    }
+    Ok(ret)
+}
+
+pub mod attr;
+pub mod auxv;
+pub mod children;
+pub mod clear_refs;
 pub mod cmdline;
 pub mod comm;
+pub mod coredump_filter;
 pub mod cwd;
 pub mod environ;
 pub mod exe;
 pub mod fd;
+pub mod gid_map;
+pub mod loginuid;
 pub mod maps;
 pub mod mem;
+pub mod mountstats;
+pub mod numa_maps;
+pub mod oom;
+pub mod pagemap;
+pub mod personality;
+pub mod projid_map;
 pub mod root;
+pub mod sched;
+pub mod schedstat;
+pub mod sessionid;
+pub mod setgroups;
+pub mod stack;
 pub mod stat;
 pub mod statm;
+pub mod syscall;
 pub mod task;
+pub mod timers;
+pub mod timerslack_ns;
+pub mod uid_map;
+pub mod wchan;
@@ -0,0 +1,573 @@
+// /proc/[pid]/mountstats (since Linux 2.6.17)
+// This file exports information (statistics, configuration
+// information) about the mounts in the process's mount namespace
+// (see mount_namespaces(7)).  Lines in this file have the form:
+//
+//     device /dev/sda7 mounted on /home with fstype ext3 [stats]
+//     (       1      )            ( 2 )             (3 ) ( 4 )
+//
+// The fields in each line are:
+//
+// (1)  The name of the mounted device (or "nodevice" if there is
+//      no corresponding device).
+//
+// (2)  The mount point within the filesystem tree.
+//
+// (3)  The filesystem type.
+//
+// (4)  Optional statistics and configuration information.
+//      Currently (as at Linux 2.6.26), only NFS filesystems export
+//      information via this field.
+//
+// This file is readable only by the owner of the process.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// The NFS section looks like:
+//
+//     device server:/export mounted on /mnt/nfs with fstype nfs4 statvers=1.1
+//         opts:   rw,vers=4.2,rsize=1048576,wsize=1048576,proto=tcp,timeo=600,retrans=2,sec=sys
+//         age:    3061
+//         caps:   caps=0x3ffbffff,wtmult=512,dtsize=32768,bsize=0,namlen=255
+//         sec:    flavor=1,pseudoflavor=1
+//         events: 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26
+//         bytes:  0 1 2 3 4 5 6 7
+//         RPC iostats version: 1.1  p/v: 100003/4 (nfs)
+//         xprt:   tcp 0 1 1 0 11 36 36 0 36 0 2 0 0
+//         per-op statistics
+//                 NULL: 1 1 0 44 24 0 0 0 0
+//                 READ: 10 10 0 1840 1049760 1 32 34 0
+//
+// Per-op fields are operations, transmissions, major timeouts, bytes sent,
+// bytes received, cumulative queue time, cumulative response time and
+// cumulative total request time in milliseconds, followed by errors since
+// RPC iostats version 1.1.
+//
+// Reference to [`fs/nfs/super.c`](https://github.com/torvalds/linux/blob/master/fs/nfs/super.c)
+// and [`net/sunrpc/stats.c`](https://github.com/torvalds/linux/blob/master/net/sunrpc/stats.c)
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+define_struct! {
+    /// Represent a mount in /proc/[pid]/mountstats.
+    pub struct MountStat {
+        /// None if there is no corresponding device.
+        device: Option<String>,
+        mount_point: PathBuf,
+        fs_type: String,
+        /// The `statvers=` value, only exported by NFS.
+        statvers: Option<String>,
+        nfs: Option<NfsStats>,
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/mountstats, returned by [`mountstats_of()`](fn.mountstats_of.html).
+    pub struct MountStats(Vec<MountStat>);
+}
+
+define_struct! {
+    /// The statistics of a NFS mount.
+    pub struct NfsStats {
+        opts: Vec<String>,
+        /// Seconds since the mount.
+        age: u64,
+        caps: BTreeMap<String, String>,
+        sec: BTreeMap<String, String>,
+        events: NfsEvents,
+        bytes: NfsBytes,
+        rpc_iostats_version: String,
+        /// The RPC program number, 100003 for NFS.
+        program: u32,
+        /// The RPC program version.
+        version: u32,
+        xprts: Vec<Xprt>,
+        ops: Vec<NfsOpStat>,
+        /// Other `key: value` lines, e.g. `impl_id`, `nfsv4` and `fsc`.
+        others: BTreeMap<String, String>,
+    }
+}
+
+impl NfsStats {
+    /// Return the value of mount option `name`, e.g. `vers` or `proto`.
+    /// Return an empty str for a flag option like `rw`.
+    pub fn opt(&self, name: &str) -> Option<&str> {
+        self.opts.iter().find_map(|o| {
+            let mut kv = o.splitn(2, '=');
+            if kv.next() == Some(name) {
+                Some(kv.next().unwrap_or(""))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Return the statistics of operation `name`, e.g. `READ` or `GETATTR`.
+    pub fn op(&self, name: &str) -> Option<&NfsOpStat> {
+        self.ops.iter().find(|o| o.name == name)
+    }
+}
+
+define_struct! {
+    /// The `events:` line of a NFS mount.
+    pub struct NfsEvents {
+        inode_revalidate: u64,
+        dentry_revalidate: u64,
+        data_invalidate: u64,
+        attr_invalidate: u64,
+        vfs_open: u64,
+        vfs_lookup: u64,
+        vfs_access: u64,
+        vfs_update_page: u64,
+        vfs_read_page: u64,
+        vfs_read_pages: u64,
+        vfs_write_page: u64,
+        vfs_write_pages: u64,
+        vfs_getdents: u64,
+        vfs_setattr: u64,
+        vfs_flush: u64,
+        vfs_fsync: u64,
+        vfs_lock: u64,
+        vfs_release: u64,
+        congestion_wait: u64,
+        setattr_trunc: u64,
+        extend_write: u64,
+        silly_rename: u64,
+        short_read: u64,
+        short_write: u64,
+        delay: u64,
+        pnfs_read: u64,
+        pnfs_write: u64,
+    }
+}
+
+define_struct! {
+    /// The `bytes:` line of a NFS mount.
+    pub struct NfsBytes {
+        normal_read_bytes: u64,
+        normal_write_bytes: u64,
+        direct_read_bytes: u64,
+        direct_write_bytes: u64,
+        server_read_bytes: u64,
+        server_write_bytes: u64,
+        read_pages: u64,
+        write_pages: u64,
+    }
+}
+
+define_struct! {
+    /// An `xprt:` line, the statistics of a RPC transport.
+    ///
+    /// The meaning of `values` depends on `protocol`, see
+    /// [`net/sunrpc/xprtsock.c`](https://github.com/torvalds/linux/blob/master/net/sunrpc/xprtsock.c).
+    pub struct Xprt {
+        /// tcp, udp or rdma.
+        protocol: String,
+        values: Vec<u64>,
+    }
+}
+
+impl Xprt {
+    fn value(&self, udp: usize, other: usize) -> Option<u64> {
+        let index = if self.protocol == "udp" { udp } else { other };
+        self.values.get(index).cloned()
+    }
+
+    /// The local port.
+    pub fn port(&self) -> Option<u64> {
+        self.values.first().cloned()
+    }
+
+    /// The number of RPC requests sent.
+    pub fn sends(&self) -> Option<u64> {
+        self.value(2, 5)
+    }
+
+    /// The number of RPC replies received.
+    pub fn recvs(&self) -> Option<u64> {
+        self.value(3, 6)
+    }
+
+    /// The number of replies with an unknown XID.
+    pub fn bad_xids(&self) -> Option<u64> {
+        self.value(4, 7)
+    }
+}
+
+define_struct! {
+    /// Statistics of a NFS operation in `per-op statistics`.
+    pub struct NfsOpStat {
+        name: String,
+        ops: u64,
+        trans: u64,
+        timeouts: u64,
+        bytes_sent: u64,
+        bytes_recv: u64,
+        /// Cumulative time requests waited in the queue, in milliseconds.
+        queue_ms: u64,
+        /// Cumulative round trip time, in milliseconds.
+        rtt_ms: u64,
+        /// Cumulative time from queueing to completion, in milliseconds.
+        execute_ms: u64,
+        /// Only available since RPC iostats version 1.1.
+        errors: Option<u64>,
+    }
+}
+
+impl NfsOpStat {
+    /// Average round trip time per operation in milliseconds, None if there is no operation.
+    pub fn avg_rtt_ms(&self) -> Option<f64> {
+        if self.ops == 0 {
+            None
+        } else {
+            Some(self.rtt_ms as f64 / self.ops as f64)
+        }
+    }
+
+    /// Average execution time per operation in milliseconds, None if there is no operation.
+    pub fn avg_execute_ms(&self) -> Option<f64> {
+        if self.ops == 0 {
+            None
+        } else {
+            Some(self.execute_ms as f64 / self.ops as f64)
+        }
+    }
+}
+
+fn parse_u64s(s: &str) -> Result<Vec<u64>, crate::ProcErr> {
+    let mut ret = vec![];
+    for v in s.split_ascii_whitespace() {
+        ret.push(v.parse::<u64>()?);
+    }
+    Ok(ret)
+}
+
+fn parse_map(s: &str) -> BTreeMap<String, String> {
+    s.split(',')
+        .filter(|kv| !kv.is_empty())
+        .map(|kv| {
+            let mut kv = kv.splitn(2, '=');
+            let k = kv.next().unwrap_or_default().to_string();
+            let v = kv.next().unwrap_or_default().to_string();
+            (k, v)
+        })
+        .collect()
+}
+
+use std::str::FromStr;
+impl FromStr for NfsEvents {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<NfsEvents, crate::ProcErr> {
+        let v = parse_u64s(s)?;
+        if v.len() < 27 {
+            return Err(format!("require 27 fields to parse NfsEvents, found {}", v.len()).into());
+        }
+        Ok(NfsEvents {
+            inode_revalidate: v[0],
+            dentry_revalidate: v[1],
+            data_invalidate: v[2],
+            attr_invalidate: v[3],
+            vfs_open: v[4],
+            vfs_lookup: v[5],
+            vfs_access: v[6],
+            vfs_update_page: v[7],
+            vfs_read_page: v[8],
+            vfs_read_pages: v[9],
+            vfs_write_page: v[10],
+            vfs_write_pages: v[11],
+            vfs_getdents: v[12],
+            vfs_setattr: v[13],
+            vfs_flush: v[14],
+            vfs_fsync: v[15],
+            vfs_lock: v[16],
+            vfs_release: v[17],
+            congestion_wait: v[18],
+            setattr_trunc: v[19],
+            extend_write: v[20],
+            silly_rename: v[21],
+            short_read: v[22],
+            short_write: v[23],
+            delay: v[24],
+            pnfs_read: v[25],
+            pnfs_write: v[26],
+        })
+    }
+}
+
+impl FromStr for NfsBytes {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<NfsBytes, crate::ProcErr> {
+        let v = parse_u64s(s)?;
+        if v.len() < 8 {
+            return Err(format!("require 8 fields to parse NfsBytes, found {}", v.len()).into());
+        }
+        Ok(NfsBytes {
+            normal_read_bytes: v[0],
+            normal_write_bytes: v[1],
+            direct_read_bytes: v[2],
+            direct_write_bytes: v[3],
+            server_read_bytes: v[4],
+            server_write_bytes: v[5],
+            read_pages: v[6],
+            write_pages: v[7],
+        })
+    }
+}
+
+impl FromStr for NfsOpStat {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<NfsOpStat, crate::ProcErr> {
+        let mut columns = s.splitn(2, ':');
+        let name = columns.next().unwrap_or_default().trim().to_string();
+        let v = parse_u64s(columns.next().ok_or("per-op statistic is empty")?)?;
+        if v.len() < 8 {
+            return Err(format!("require 8 fields to parse NfsOpStat, found {}", v.len()).into());
+        }
+        Ok(NfsOpStat {
+            name,
+            ops: v[0],
+            trans: v[1],
+            timeouts: v[2],
+            bytes_sent: v[3],
+            bytes_recv: v[4],
+            queue_ms: v[5],
+            rtt_ms: v[6],
+            execute_ms: v[7],
+            errors: v.get(8).cloned(),
+        })
+    }
+}
+
+// `RPC iostats version: 1.1  p/v: 100003/4 (nfs)`
+fn parse_rpc_iostats(s: &str) -> Result<(String, u32, u32), crate::ProcErr> {
+    let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+    if columns.len() < 6 || columns[4] != "p/v:" {
+        return Err(format!("unknow RPC iostats line: {}", s).into());
+    }
+    let mut pv = columns[5].splitn(2, '/');
+    let program = pv.next().unwrap_or_default().parse::<u32>()?;
+    let version = pv.next().ok_or("RPC version not found")?.parse::<u32>()?;
+    Ok((columns[3].to_string(), program, version))
+}
+
+impl NfsStats {
+    fn from_lines(lines: &[&str]) -> Result<NfsStats, crate::ProcErr> {
+        let mut opts = vec![];
+        let mut age = None;
+        let mut caps = BTreeMap::new();
+        let mut sec = BTreeMap::new();
+        let mut events = None;
+        let mut bytes = None;
+        let mut rpc = None;
+        let mut xprts = vec![];
+        let mut ops = vec![];
+        let mut others = BTreeMap::new();
+
+        let mut per_op = false;
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if per_op {
+                ops.push(line.parse::<NfsOpStat>()?);
+                continue;
+            }
+            if line == "per-op statistics" {
+                per_op = true;
+                continue;
+            }
+            if line.starts_with("RPC iostats version:") {
+                rpc = Some(parse_rpc_iostats(line)?);
+                continue;
+            }
+
+            let mut kv = line.splitn(2, ':');
+            let key = kv.next().unwrap_or_default();
+            let value = kv.next().unwrap_or_default().trim();
+            match key {
+                "opts" => opts = value.split(',').map(|o| o.to_string()).collect(),
+                "age" => age = Some(value.parse::<u64>()?),
+                "caps" => caps = parse_map(value),
+                "sec" => sec = parse_map(value),
+                "events" => events = Some(value.parse::<NfsEvents>()?),
+                "bytes" => bytes = Some(value.parse::<NfsBytes>()?),
+                "xprt" => {
+                    let mut columns = value.splitn(2, |c: char| c.is_ascii_whitespace());
+                    let protocol = columns.next().unwrap_or_default().to_string();
+                    let values = parse_u64s(columns.next().unwrap_or_default())?;
+                    xprts.push(Xprt { protocol, values });
+                }
+                _ => {
+                    others.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        let (rpc_iostats_version, program, version) = rpc.ok_or("RPC iostats not found")?;
+        Ok(NfsStats {
+            opts,
+            age: age.ok_or("age not found")?,
+            caps,
+            sec,
+            events: events.ok_or("events not found")?,
+            bytes: bytes.ok_or("bytes not found")?,
+            rpc_iostats_version,
+            program,
+            version,
+            xprts,
+            ops,
+            others,
+        })
+    }
+}
+
+impl MountStat {
+    fn from_lines(header: &str, lines: &[&str]) -> Result<MountStat, crate::ProcErr> {
+        let columns: Vec<&str> = header.split_ascii_whitespace().collect();
+        // `device <dev> mounted on ...` or `no device mounted on ...`
+        let (device, rest) = match columns.as_slice() {
+            ["device", dev, rest @ ..] => (Some(dev.to_string()), rest),
+            ["no", "device", rest @ ..] => (None, rest),
+            _ => return Err(format!("unknow mountstats line: {}", header).into()),
+        };
+        let (mount_point, fs_type, statvers) = match rest {
+            ["mounted", "on", mp, "with", "fstype", fs_type, others @ ..] => {
+                let statvers = others
+                    .iter()
+                    .find_map(|o| o.strip_prefix("statvers="))
+                    .map(|v| v.to_string());
+                (PathBuf::from(mp), fs_type.to_string(), statvers)
+            }
+            _ => return Err(format!("unknow mountstats line: {}", header).into()),
+        };
+
+        let nfs = if fs_type.starts_with("nfs") && statvers.is_some() {
+            Some(NfsStats::from_lines(lines)?)
+        } else {
+            None
+        };
+
+        Ok(MountStat {
+            device,
+            mount_point,
+            fs_type,
+            statvers,
+            nfs,
+        })
+    }
+}
+
+impl FromStr for MountStats {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<MountStats, crate::ProcErr> {
+        let mut ret = vec![];
+        let mut header: Option<&str> = None;
+        let mut body: Vec<&str> = vec![];
+        for line in s.lines() {
+            if line.starts_with("device ") || line.starts_with("no device ") {
+                if let Some(h) = header {
+                    ret.push(MountStat::from_lines(h, &body)?);
+                }
+                header = Some(line);
+                body.clear();
+            } else if !line.trim().is_empty() {
+                body.push(line);
+            }
+        }
+        if let Some(h) = header {
+            ret.push(MountStat::from_lines(h, &body)?);
+        }
+        Ok(MountStats(ret))
+    }
+}
+
+pid_instance_impl! {
+    mountstats_of, "mountstats", MountStats,
+    mountstats_self, mountstats_of_of, mountstats_self_of, mountstats_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_mountstats() {
+        let source = "device rootfs mounted on / with fstype rootfs
+device proc mounted on /proc with fstype proc
+no device mounted on /mnt/none with fstype tmpfs
+device server:/export mounted on /mnt/nfs with fstype nfs4 statvers=1.1
+\topts:\trw,vers=4.2,rsize=1048576,wsize=1048576,namlen=255,acregmin=3,hard,proto=tcp,timeo=600,retrans=2,sec=sys
+\tage:\t3061
+\timpl_id:\tname='',domain='',date='0,0'
+\tcaps:\tcaps=0x3ffbffff,wtmult=512,dtsize=32768,bsize=0,namlen=255
+\tnfsv4:\tbm0=0xfdffbfff,bm1=0x40f9be3e,bm2=0x60800,acl=0x3,sessions,pnfs=not configured,lease_time=90,lease_expired=0
+\tsec:\tflavor=1,pseudoflavor=1
+\tevents:\t0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26
+\tbytes:\t100 200 0 0 300 400 10 20
+\tRPC iostats version: 1.1  p/v: 100003/4 (nfs)
+\txprt:\ttcp 875 1 2 0 11 36 36 0 36 0 2 0 0
+\tper-op statistics
+\t        NULL: 1 1 0 44 24 0 0 0 0
+\t        READ: 10 10 0 1840 1049760 1 32 34 0
+\t       WRITE: 0 0 0 0 0 0 0 0 0
+
+device server:/old mounted on /mnt/old with fstype nfs statvers=1.0
+\topts:\tro,vers=3
+\tage:\t5
+\tcaps:\tcaps=0x3fc7
+\tsec:\tflavor=1
+\tevents:\t0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+\tbytes:\t0 0 0 0 0 0 0 0
+\tRPC iostats version: 1.0  p/v: 100003/3 (nfs)
+\txprt:\tudp 0 0 4 4 0 4 0 0 0
+\tper-op statistics
+\t        NULL: 0 0 0 0 0 0 0 0
+";
+        let stats = source.parse::<MountStats>().unwrap();
+        assert_eq!(5, stats.len());
+        assert_eq!(&Some("rootfs".to_string()), stats[0].device());
+        assert_eq!(None, stats[1].nfs);
+        assert_eq!(None, stats[2].device);
+        assert_eq!(&PathBuf::from("/mnt/none"), stats[2].mount_point());
+
+        let nfs = stats[3].nfs().as_ref().unwrap();
+        assert_eq!(&Some("1.1".to_string()), stats[3].statvers());
+        assert_eq!("nfs4", stats[3].fs_type());
+        assert_eq!(Some("4.2"), nfs.opt("vers"));
+        assert_eq!(Some(""), nfs.opt("hard"));
+        assert_eq!(None, nfs.opt("soft"));
+        assert_eq!(3061, *nfs.age());
+        assert_eq!("0x3ffbffff", nfs.caps()["caps"]);
+        assert_eq!("1", nfs.sec()["flavor"]);
+        assert_eq!(5, *nfs.events().vfs_lookup());
+        assert_eq!(26, *nfs.events().pnfs_write());
+        assert_eq!(300, *nfs.bytes().server_read_bytes());
+        assert_eq!("1.1", nfs.rpc_iostats_version());
+        assert_eq!((100003, 4), (*nfs.program(), *nfs.version()));
+        assert!(nfs.others().contains_key("nfsv4"));
+
+        let xprt = &nfs.xprts()[0];
+        assert_eq!("tcp", xprt.protocol());
+        assert_eq!(Some(875), xprt.port());
+        assert_eq!(Some(36), xprt.sends());
+        assert_eq!(Some(0), xprt.bad_xids());
+
+        assert_eq!(3, nfs.ops().len());
+        let read = nfs.op("READ").unwrap();
+        assert_eq!(1049760, *read.bytes_recv());
+        assert_eq!(Some(0), *read.errors());
+        assert_eq!(Some(3.2), read.avg_rtt_ms());
+        assert_eq!(Some(3.4), read.avg_execute_ms());
+        assert_eq!(None, nfs.op("WRITE").unwrap().avg_rtt_ms());
+
+        let old = stats[4].nfs().as_ref().unwrap();
+        assert_eq!(Some(4), old.xprts()[0].sends());
+        assert_eq!(None, old.ops()[0].errors);
+    }
+}
@@ -0,0 +1,326 @@
+// /proc/[pid]/numa_maps (since Linux 2.6.14)
+// See numa(7).
+//
+// For each memory range, the first field of each line shows the
+// starting address of the memory range.  This field is followed
+// by the memory policy in effect for that range; see
+// set_mempolicy(2) and mbind(2).  Additional fields on each line
+// give information about the pages in the range:
+//
+// N<node>=<nr_pages>
+//        The number of pages allocated on <node>.  <nr_pages>
+//        includes only pages currently mapped by the process.
+//        Page migration and memory reclaim may have temporarily
+//        unmapped pages associated with this memory range.
+//        These pages may show up again only after the process
+//        has attempted to reference them.  If the memory range
+//        represents a shared memory area or file mapping, other
+//        processes may currently have additional pages mapped
+//        in a corresponding memory range.
+//
+// file=<filename>
+//        The file backing the memory range.  If the file is
+//        mapped as private, write accesses may have generated
+//        COW (Copy-On-Write) pages in this memory range.  These
+//        pages are displayed as anonymous pages.
+//
+// heap   Memory range is used for the heap.
+//
+// stack  Memory range is used for the stack.
+//
+// huge   Huge memory range.  The page counts shown are huge
+//        pages and not regular sized pages.
+//
+// anon=<pages>
+//        The number of anonymous page in the range.
+//
+// dirty=<pages>
+//        Number of dirty pages.
+//
+// mapped=<pages>
+//        Total number of mapped pages, if different from
+//        dirty and anon pages.
+//
+// mapmax=<count>
+//        Maximum mapcount (number of processes mapping a sin‐
+//        gle page) encountered during the scan.  This may be
+//        used as an indicator of the degree of sharing occur‐
+//        ring in a given memory range.
+//
+// swapcache=<count>
+//        Number of pages that have an associated entry on a
+//        swap device.
+//
+// active=<pages>
+//        The number of pages on the active list.  This field
+//        is shown only if different from the number of pages
+//        in this range.  This means that some inactive pages
+//        exist in the memory range that may be removed from
+//        memory by the swapper soon.
+//
+// writeback=<pages>
+//        Number of pages that are currently being written out
+//        to disk.
+//
+// -- http://man7.org/linux/man-pages/man7/numa.7.html
+//
+// 55aeea88a000 default file=/usr/bin/cat anon=1 dirty=1 active=0 N0=1 kernelpagesize_kB=4
+// 7fa77903d000 interleave:0-1 file=/usr/lib/libc.so.6 mapped=37 mapmax=5 N0=20 N1=17 kernelpagesize_kB=4
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The memory policy of a memory range, see set_mempolicy(2).
+///
+/// The node list (e.g. `0-1`) is kept as it is,
+/// mode flags such as `=static` and `=relative` are in [`NumaMap::policy_flags`](struct.NumaMap.html#method.policy_flags).
+#[derive(Debug, PartialEq, Clone)]
+pub enum Policy {
+    Default,
+    Local,
+    Prefer(String),
+    PreferredMany(String),
+    Bind(String),
+    Interleave(String),
+    WeightedInterleave(String),
+    Unknown(String),
+}
+
+impl Policy {
+    /// Split the policy and its mode flags at the beginning of `s` and return the rest.
+    ///
+    /// Some policy names contain spaces, so it can't be splitted by whitespace.
+    fn split(s: &str) -> (Policy, Vec<String>, &str) {
+        const MULTI_WORD: [&str; 2] = ["weighted interleave", "prefer (many)"];
+        let mut policy_len = s.find(' ').unwrap_or(s.len());
+        for name in MULTI_WORD.iter() {
+            if let Some(rest) = s.strip_prefix(*name) {
+                policy_len = rest.find(' ').map_or(s.len(), |p| p + name.len());
+            }
+        }
+        let policy_str = &s[..policy_len];
+        let mut kv = policy_str.splitn(2, |c| [':', '='].contains(&c));
+        let mode = kv.next().unwrap_or_default();
+        // e.g. `bind=static:1` or `interleave=relative|balancing:0-1`.
+        let mut rest = policy_str[mode.len()..].splitn(2, ':');
+        let flags = match rest.next() {
+            Some(f) if f.starts_with('=') => f[1..].split('|').map(|f| f.to_string()).collect(),
+            _ => vec![],
+        };
+        let nodes = rest.next().unwrap_or_default().to_string();
+        let policy = match mode {
+            "default" => Policy::Default,
+            "local" => Policy::Local,
+            "prefer" => Policy::Prefer(nodes),
+            "prefer (many)" => Policy::PreferredMany(nodes),
+            "bind" => Policy::Bind(nodes),
+            "interleave" => Policy::Interleave(nodes),
+            "weighted interleave" => Policy::WeightedInterleave(nodes),
+            _ => Policy::Unknown(policy_str.to_string()),
+        };
+        (policy, flags, &s[policy_len..])
+    }
+}
+
+define_struct! {
+    /// Represent a line in /proc/[pid]/numa_maps.
+    ///
+    /// Page counts are 0 if they are not shown.
+    pub struct NumaMap {
+        address: usize,
+        policy: Policy,
+        /// The mode flags of the policy, e.g. `static`, `relative` and `balancing`.
+        policy_flags: Vec<String>,
+        file: Option<PathBuf>,
+        heap: bool,
+        stack: bool,
+        huge: bool,
+        anon: usize,
+        dirty: usize,
+        mapped: usize,
+        mapmax: usize,
+        swapcache: usize,
+        active: Option<usize>,
+        writeback: usize,
+        /// Pages allocated on each node, indexed by node number.
+        nodes: BTreeMap<usize, usize>,
+        kernel_page_size_kb: usize,
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for NumaMap {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<NumaMap, crate::ProcErr> {
+        let s = s.trim();
+        let p = s.find(' ').ok_or("policy not found")?;
+        let address = usize::from_str_radix(&s[..p], 16)?;
+        let (policy, policy_flags, rest) = Policy::split(&s[p + 1..]);
+
+        let mut numa_map = NumaMap {
+            address,
+            policy,
+            policy_flags,
+            file: None,
+            heap: false,
+            stack: false,
+            huge: false,
+            anon: 0,
+            dirty: 0,
+            mapped: 0,
+            mapmax: 0,
+            swapcache: 0,
+            active: None,
+            writeback: 0,
+            nodes: BTreeMap::new(),
+            kernel_page_size_kb: 0,
+        };
+        for item in rest.split_ascii_whitespace() {
+            let mut kv = item.splitn(2, '=');
+            let key = kv.next().unwrap_or_default();
+            let value = kv.next();
+            match (key, value) {
+                ("heap", None) => numa_map.heap = true,
+                ("stack", None) => numa_map.stack = true,
+                ("huge", None) => numa_map.huge = true,
+                ("file", Some(v)) => numa_map.file = Some(v.into()),
+                ("anon", Some(v)) => numa_map.anon = v.parse()?,
+                ("dirty", Some(v)) => numa_map.dirty = v.parse()?,
+                ("mapped", Some(v)) => numa_map.mapped = v.parse()?,
+                ("mapmax", Some(v)) => numa_map.mapmax = v.parse()?,
+                ("swapcache", Some(v)) => numa_map.swapcache = v.parse()?,
+                ("active", Some(v)) => numa_map.active = Some(v.parse()?),
+                ("writeback", Some(v)) => numa_map.writeback = v.parse()?,
+                ("kernelpagesize_kB", Some(v)) => numa_map.kernel_page_size_kb = v.parse()?,
+                (k, Some(v)) if k.starts_with('N') => {
+                    let node = k[1..].parse::<usize>()?;
+                    numa_map.nodes.insert(node, v.parse()?);
+                }
+                // file name may contains spaces.
+                (_, None) if numa_map.file.is_some() => {
+                    let file = numa_map.file.take().unwrap_or_default();
+                    numa_map.file = Some(format!("{} {}", file.display(), item).into());
+                }
+                _ => return Err(format!("unknow numa_maps field: {}", item).into()),
+            }
+        }
+        Ok(numa_map)
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/numa_maps, returned by [`numa_maps_of()`](fn.numa_maps_of.html).
+    pub struct NumaMaps(Vec<NumaMap>);
+}
+
+impl NumaMaps {
+    /// Return the number of pages on each node of all memory ranges.
+    ///
+    /// Pages in huge memory ranges are counted as huge pages, see [`node_bytes()`](#method.node_bytes).
+    pub fn node_pages(&self) -> BTreeMap<usize, usize> {
+        let mut ret = BTreeMap::new();
+        for map in self.0.iter() {
+            for (node, pages) in map.nodes.iter() {
+                *ret.entry(*node).or_insert(0) += pages;
+            }
+        }
+        ret
+    }
+
+    /// Return the memory size in bytes on each node of all memory ranges.
+    pub fn node_bytes(&self) -> BTreeMap<usize, usize> {
+        let mut ret = BTreeMap::new();
+        for map in self.0.iter() {
+            for (node, pages) in map.nodes.iter() {
+                *ret.entry(*node).or_insert(0) += pages * map.kernel_page_size_kb * 1024;
+            }
+        }
+        ret
+    }
+}
+
+impl FromStr for NumaMaps {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<NumaMaps, crate::ProcErr> {
+        let mut v = vec![];
+        for line in s.lines() {
+            v.push(line.parse::<NumaMap>()?);
+        }
+        Ok(NumaMaps(v))
+    }
+}
+
+pid_instance_impl! {
+    numa_maps_of, "numa_maps", NumaMaps,
+    numa_maps_self, numa_maps_of_of, numa_maps_self_of, numa_maps_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_numa_map() {
+        let source = "7fa77903d000 interleave:0-1 file=/usr/lib/libc.so.6 mapped=37 mapmax=5 N0=20 N1=17 kernelpagesize_kB=4";
+        let mut nodes = BTreeMap::new();
+        nodes.insert(0, 20);
+        nodes.insert(1, 17);
+        let correct = NumaMap {
+            address: 0x7fa77903d000,
+            policy: Policy::Interleave("0-1".to_string()),
+            policy_flags: vec![],
+            file: Some("/usr/lib/libc.so.6".into()),
+            heap: false,
+            stack: false,
+            huge: false,
+            anon: 0,
+            dirty: 0,
+            mapped: 37,
+            mapmax: 5,
+            swapcache: 0,
+            active: None,
+            writeback: 0,
+            nodes,
+            kernel_page_size_kb: 4,
+        };
+        assert_eq!(correct, source.parse::<NumaMap>().unwrap());
+
+        let source = "55af02ab4000 weighted interleave:0,2 heap anon=1 dirty=1 active=0 N0=1 kernelpagesize_kB=4";
+        let numa_map = source.parse::<NumaMap>().unwrap();
+        assert_eq!(
+            Policy::WeightedInterleave("0,2".to_string()),
+            numa_map.policy
+        );
+        assert!(numa_map.heap);
+        assert_eq!(Some(0), numa_map.active);
+
+        let source = "7f0000000000 bind=static:1 anon=2 N1=2 kernelpagesize_kB=4";
+        let numa_map = source.parse::<NumaMap>().unwrap();
+        assert_eq!(Policy::Bind("1".to_string()), numa_map.policy);
+        assert_eq!(vec!["static".to_string()], numa_map.policy_flags);
+
+        let source =
+            "7f0000000000 interleave=relative|balancing:0-1 anon=2 N1=2 kernelpagesize_kB=4";
+        let numa_map = source.parse::<NumaMap>().unwrap();
+        assert_eq!(Policy::Interleave("0-1".to_string()), numa_map.policy);
+        assert_eq!(
+            vec!["relative".to_string(), "balancing".to_string()],
+            numa_map.policy_flags
+        );
+    }
+
+    #[test]
+    fn test_node_summary() {
+        let source = "7f0000000000 default anon=2 N0=1 N1=1 kernelpagesize_kB=4
+7f0000200000 default huge anon=1 N1=1 kernelpagesize_kB=2048";
+        let numa_maps = source.parse::<NumaMaps>().unwrap();
+        let pages = numa_maps.node_pages();
+        assert_eq!(Some(&1), pages.get(&0));
+        assert_eq!(Some(&2), pages.get(&1));
+        let bytes = numa_maps.node_bytes();
+        assert_eq!(Some(&4096), bytes.get(&0));
+        assert_eq!(Some(&(4096 + 2048 * 1024)), bytes.get(&1));
+    }
+}
@@ -0,0 +1,197 @@
+// /proc/[pid]/oom_adj (since Linux 2.6.11)
+// This file can be used to adjust the score used to select which
+// process should be killed in an out-of-memory (OOM) situation.
+// The kernel uses this value for a bit-shift operation of the
+// process's oom_score value: valid values are in the range -16
+// to +15, plus the special value -17, which disables OOM-killing
+// altogether for this process.  A positive score increases the
+// likelihood of this process being killed by the OOM-killer; a
+// negative score decreases the likelihood.
+//
+// Since Linux 2.6.36, use of this file is deprecated in favor of
+// /proc/[pid]/oom_score_adj.
+//
+// /proc/[pid]/oom_score (since Linux 2.6.11)
+// This file displays the current score that the kernel gives to
+// this process for the purpose of selecting a process for the
+// OOM-killer.  A higher score means that the process is more
+// likely to be selected by the OOM-killer.  The basis for this
+// score is the amount of memory used by the process, with
+// increases (+) or decreases (-) for factors including:
+//
+// * whether the process is privileged (-).
+//
+// Before kernel 2.6.36 the following factors were also used in
+// the calculation of oom_score:
+//
+// * whether the process creates a lot of children using fork(2)
+//   (+);
+//
+// * whether the process has been running a long time, or has
+//   used a lot of CPU time (-);
+//
+// * whether the process has a low nice value (i.e., > 0) (+);
+//   and
+//
+// * whether the process is making direct hardware access (-).
+//
+// The oom_score also reflects the adjustment specified by the
+// oom_score_adj or oom_adj setting for the process.
+//
+// /proc/[pid]/oom_score_adj (since Linux 2.6.36)
+// This file can be used to adjust the badness heuristic used to
+// select which process gets killed in out-of-memory conditions.
+//
+// The badness heuristic assigns a value to each candidate task
+// ranging from 0 (never kill) to 1000 (always kill) to determine
+// which process is targeted.  The units are roughly a proportion
+// along that range of allowed memory the process may allocate
+// from, based on an estimation of its current memory and swap
+// use.  For example, if a task is using all allowed memory, its
+// badness score will be 1000.  If it is using half of its
+// allowed memory, its score will be 500.
+//
+// The value of oom_score_adj is added to the badness score
+// before it is used to determine which task to kill.  Acceptable
+// values range from -1000 (OOM_SCORE_ADJ_MIN) to +1000
+// (OOM_SCORE_ADJ_MAX).  This allows user space to control the
+// preference for OOM-killing, ranging from always preferring a
+// certain task or completely disabling it from OOM killing.  The
+// lowest possible value, -1000, is equivalent to disabling OOM-
+// killing entirely for that task, since it will always report a
+// badness score of 0.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::pid::Pid;
+
+/// The minimum value of oom_score_adj, which disables OOM-killing for the process.
+pub const OOM_SCORE_ADJ_MIN: i32 = -1000;
+/// The maximum value of oom_score_adj.
+pub const OOM_SCORE_ADJ_MAX: i32 = 1000;
+
+fn read_value<T>(path: &str) -> Result<T, crate::ProcErr>
+where
+    T: std::str::FromStr,
+    crate::ProcErr: From<T::Err>,
+{
+    let content = std::fs::read_to_string(path)?;
+    Ok(content.trim().parse::<T>()?)
+}
+
+fn write_oom_score_adj(path: &str, value: i32) -> Result<(), crate::ProcErr> {
+    if !(OOM_SCORE_ADJ_MIN..=OOM_SCORE_ADJ_MAX).contains(&value) {
+        let emsg = format!(
+            "oom_score_adj should be in range [{}, {}], but got {}",
+            OOM_SCORE_ADJ_MIN, OOM_SCORE_ADJ_MAX, value
+        );
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, emsg).into());
+    }
+    std::fs::write(path, value.to_string())?;
+    Ok(())
+}
+
+/// Return the content of /proc/[pid]/oom_score.
+pub fn oom_score_of(pid: Pid) -> Result<u32, crate::ProcErr> {
+    read_value(&format!("/proc/{}/oom_score", pid))
+}
+
+/// Return the content of /proc/self/oom_score.
+pub fn oom_score_self() -> Result<u32, crate::ProcErr> {
+    read_value("/proc/self/oom_score")
+}
+
+/// Return the content of /proc/[pid]/oom_score_adj.
+pub fn oom_score_adj_of(pid: Pid) -> Result<i32, crate::ProcErr> {
+    read_value(&format!("/proc/{}/oom_score_adj", pid))
+}
+
+/// Return the content of /proc/self/oom_score_adj.
+pub fn oom_score_adj_self() -> Result<i32, crate::ProcErr> {
+    read_value("/proc/self/oom_score_adj")
+}
+
+/// Return the content of /proc/[pid]/oom_adj.
+///
+/// This file is deprecated since Linux 2.6.36, use [`oom_score_adj_of()`](fn.oom_score_adj_of.html) instead.
+pub fn oom_adj_of(pid: Pid) -> Result<i32, crate::ProcErr> {
+    read_value(&format!("/proc/{}/oom_adj", pid))
+}
+
+/// Return the content of /proc/self/oom_adj.
+///
+/// This file is deprecated since Linux 2.6.36, use [`oom_score_adj_self()`](fn.oom_score_adj_self.html) instead.
+pub fn oom_adj_self() -> Result<i32, crate::ProcErr> {
+    read_value("/proc/self/oom_adj")
+}
+
+/// Write `value` to /proc/[pid]/oom_score_adj.
+///
+/// An `InvalidInput` IO error is returned if `value` is out of
+/// [`OOM_SCORE_ADJ_MIN`, `OOM_SCORE_ADJ_MAX`], which is the same as what the kernel does.
+/// Decreasing the value below the one set by a privileged process requires CAP_SYS_RESOURCE.
+pub fn set_oom_score_adj_of(pid: Pid, value: i32) -> Result<(), crate::ProcErr> {
+    write_oom_score_adj(&format!("/proc/{}/oom_score_adj", pid), value)
+}
+
+/// Write `value` to /proc/self/oom_score_adj.
+///
+/// See [`set_oom_score_adj_of()`](fn.set_oom_score_adj_of.html) for details.
+pub fn set_oom_score_adj_self(value: i32) -> Result<(), crate::ProcErr> {
+    write_oom_score_adj("/proc/self/oom_score_adj", value)
+}
+
+/// Return all processes with their oom_score, the highest score comes first.
+///
+/// Processes which exit while reading are skipped.
+pub fn oom_ranking() -> Result<Vec<(Pid, u32)>, crate::ProcErr> {
+    let mut ranking = vec![];
+    for pid in crate::pid::pids()? {
+        match oom_score_of(pid) {
+            Ok(score) => ranking.push((pid, score)),
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => (),
+            Err(e) => return Err(e),
+        }
+    }
+    ranking.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    Ok(ranking)
+}
+
+/// Return the process which is most likely to be killed by the OOM-killer next.
+///
+/// Processes with a score of 0 (such as kernel threads and
+/// processes with oom_score_adj of -1000) are never selected.
+pub fn next_oom_victim() -> Result<Option<(Pid, u32)>, crate::ProcErr> {
+    let ranking = oom_ranking()?;
+    Ok(ranking.into_iter().find(|(_, score)| *score > 0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_oom_self() {
+        oom_score_self().unwrap();
+        let adj = oom_score_adj_self().unwrap();
+        assert!((OOM_SCORE_ADJ_MIN..=OOM_SCORE_ADJ_MAX).contains(&adj));
+        // increasing oom_score_adj is always permitted.
+        set_oom_score_adj_self(adj).unwrap();
+        assert_eq!(adj, oom_score_adj_self().unwrap());
+    }
+
+    #[test]
+    fn test_set_oom_score_adj_out_of_range() {
+        match set_oom_score_adj_self(1001) {
+            Err(crate::ProcErr::IO(e)) => assert_eq!(std::io::ErrorKind::InvalidInput, e.kind()),
+            x => panic!("unexpected result: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_oom_ranking() {
+        let ranking = oom_ranking().unwrap();
+        assert!(ranking.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(ranking.iter().any(|(pid, _)| *pid == std::process::id()));
+    }
+}
@@ -0,0 +1,380 @@
+// /proc/[pid]/pagemap (since Linux 2.6.25)
+// This file shows the mapping of each of the process's virtual
+// pages into physical page frames or swap area.  It contains one
+// 64-bit value for each virtual page, with the bits set as fol‐
+// lows:
+//
+//      63     If set, the page is present in RAM.
+//
+//      62     If set, the page is in swap space
+//
+//      61 (since Linux 3.5)
+//             The page is a file-mapped page or a shared anony‐
+//             mous page.
+//
+//      60–57 (since Linux 3.11)
+//             Zero
+//
+//      56 (since Linux 4.2)
+//             The page is exclusively mapped.
+//
+//      55 (since Linux 3.11)
+//             PTE is soft-dirty (see the kernel source file Docu‐
+//             mentation/admin-guide/mm/soft-dirty.rst).
+//
+//      54–0   If the page is present in RAM (bit 63), then these
+//             bits provide the page frame number, which can be
+//             used to index /proc/kpageflags and /proc/kpage‐
+//             count.  If the page is present in swap (bit 62),
+//             then bits 4–0 give the swap type, and bits 54–5
+//             encode the swap offset.
+//
+// Before Linux 3.11, bits 60–55 were used to encode the base-2
+// log of the page size.
+//
+// To employ /proc/[pid]/pagemap efficiently, use
+// /proc/[pid]/maps to determine which areas of memory are actu‐
+// ally mapped and seek to skip over unmapped regions.
+//
+// The /proc/[pid]/pagemap file is present only if the CON‐
+// FIG_PROC_PAGE_MONITOR kernel configuration option is enabled.
+//
+// Permission to access this file is governed by a ptrace access
+// mode PTRACE_MODE_READ_FSCREDS check; see ptrace(2).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// Since Linux 4.0 only users with the CAP_SYS_ADMIN capability can get PFNs.
+// In 4.0 and 4.1 opens by unprivileged fail with -EPERM.  Starting from
+// 4.2 the PFN field is zeroed if the user does not have CAP_SYS_ADMIN.
+//
+// -- https://www.kernel.org/doc/Documentation/admin-guide/mm/pagemap.rst
+
+use crate::pid::maps::Map;
+use crate::pid::Pid;
+use crate::proc::kpageflags::{read_u64_entries, KPageFlags};
+use std::fs::File;
+
+define_struct! {
+    /// Represent an entry of /proc/[pid]/pagemap, returned by [`pagemap_of()`](fn.pagemap_of.html).
+    pub struct PageMapEntry(u64);
+}
+
+impl PageMapEntry {
+    const PFN_MASK: u64 = (1 << 55) - 1;
+
+    /// The page is present in RAM.
+    pub fn present(&self) -> bool {
+        self.0 & (1 << 63) != 0
+    }
+
+    /// The page is in swap space.
+    pub fn swapped(&self) -> bool {
+        self.0 & (1 << 62) != 0
+    }
+
+    /// The page is a file-mapped page or a shared anonymous page.
+    pub fn file_or_shared_anon(&self) -> bool {
+        self.0 & (1 << 61) != 0
+    }
+
+    /// The page is exclusively mapped.
+    pub fn exclusive(&self) -> bool {
+        self.0 & (1 << 56) != 0
+    }
+
+    /// The PTE is soft-dirty.
+    pub fn soft_dirty(&self) -> bool {
+        self.0 & (1 << 55) != 0
+    }
+
+    /// Return the page frame number if the page is present in RAM.
+    ///
+    /// The PFN is zeroed without CAP_SYS_ADMIN, None is returned in this case.
+    pub fn pfn(&self) -> Option<u64> {
+        let pfn = self.0 & Self::PFN_MASK;
+        if self.present() && pfn != 0 {
+            Some(pfn)
+        } else {
+            None
+        }
+    }
+
+    /// Return the swap type if the page is in swap space.
+    pub fn swap_type(&self) -> Option<u64> {
+        if self.swapped() {
+            Some(self.0 & 0x1f)
+        } else {
+            None
+        }
+    }
+
+    /// Return the swap offset if the page is in swap space.
+    pub fn swap_offset(&self) -> Option<u64> {
+        if self.swapped() {
+            Some((self.0 & Self::PFN_MASK) >> 5)
+        } else {
+            None
+        }
+    }
+}
+
+/// The number of entries read at once, 32KB.
+const CHUNK_PAGES: usize = 4096;
+
+/// Read entries of the pagemap file `path` for the virtual address range `[start, end)`
+/// in chunks, `f` is called with each chunk.
+pub(crate) fn pagemap_chunks<F>(
+    path: &str,
+    address: (usize, usize),
+    mut f: F,
+) -> Result<(), crate::ProcErr>
+where
+    F: FnMut(&[PageMapEntry]) -> Result<(), crate::ProcErr>,
+{
+    let page_size = crate::pid::auxv::page_size()?;
+    let file = File::open(path)?;
+    let mut start = address.0 / page_size;
+    let end = address.1.div_ceil(page_size);
+    while start < end {
+        let count = (end - start).min(CHUNK_PAGES);
+        let entries: Vec<PageMapEntry> = read_u64_entries(&file, start as u64, count)?
+            .into_iter()
+            .map(PageMapEntry)
+            .collect();
+        f(&entries)?;
+        start += count;
+    }
+    Ok(())
+}
+
+fn pagemap_impl(path: &str, address: (usize, usize)) -> Result<Vec<PageMapEntry>, crate::ProcErr> {
+    let mut ret = vec![];
+    pagemap_chunks(path, address, |entries| {
+        ret.extend_from_slice(entries);
+        Ok(())
+    })?;
+    Ok(ret)
+}
+
+/// Return entries of /proc/[pid]/pagemap for each page in the virtual address range `[start, end)`.
+///
+/// The range is usually the [`address`](../maps/struct.Map.html#method.address) of a mapping.
+pub fn pagemap_of(pid: Pid, address: (usize, usize)) -> Result<Vec<PageMapEntry>, crate::ProcErr> {
+    pagemap_impl(&format!("/proc/{}/pagemap", pid), address)
+}
+
+/// Return entries of /proc/self/pagemap for each page in the virtual address range `[start, end)`.
+pub fn pagemap_self(address: (usize, usize)) -> Result<Vec<PageMapEntry>, crate::ProcErr> {
+    pagemap_impl("/proc/self/pagemap", address)
+}
+
+define_struct! {
+    /// Page statistics of a mapping, returned by [`page_summary_of()`](fn.page_summary_of.html).
+    ///
+    /// All fields are numbers of pages.
+    /// The fields after `file_or_shared_anon` are collected from /proc/kpageflags and /proc/kpagecount,
+    /// they are 0 if `pfn_available` is false.
+    pub struct PageSummary {
+        pages: usize,
+        present: usize,
+        swapped: usize,
+        soft_dirty: usize,
+        exclusive: usize,
+        file_or_shared_anon: usize,
+        /// Whether the PFNs are visible to current process, which requires CAP_SYS_ADMIN.
+        pfn_available: bool,
+        /// Pages belong to a hugetlbfs page.
+        huge: usize,
+        /// Pages belong to a transparent huge page.
+        thp: usize,
+        ksm: usize,
+        zero_page: usize,
+        /// Pages mapped more than once.
+        shared: usize,
+    }
+}
+
+// Return true if PFNs are visible to current process, by probing a page of the stack.
+fn pfn_visible() -> Result<bool, crate::ProcErr> {
+    let probe = 1u8;
+    let address = &probe as *const u8 as usize;
+    unsafe { std::ptr::read_volatile(&probe) };
+    let entries = pagemap_self((address, address + 1))?;
+    Ok(entries.first().and_then(|e| e.pfn()).is_some())
+}
+
+// Read entries of sorted and deduplicated `pfns` from /proc/kpageflags or /proc/kpagecount,
+// a contiguous run of page frames is read at once.
+fn read_pfn_entries(file: &File, pfns: &[u64]) -> Result<Vec<u64>, crate::ProcErr> {
+    let mut ret = Vec::with_capacity(pfns.len());
+    let mut i = 0;
+    while i < pfns.len() {
+        let mut len = 1;
+        while i + len < pfns.len() && pfns[i + len] == pfns[i] + len as u64 {
+            len += 1;
+        }
+        ret.extend(read_u64_entries(file, pfns[i], len)?);
+        i += len;
+    }
+    Ok(ret)
+}
+
+impl PageSummary {
+    fn new(pfn_available: bool) -> PageSummary {
+        PageSummary {
+            pages: 0,
+            present: 0,
+            swapped: 0,
+            soft_dirty: 0,
+            exclusive: 0,
+            file_or_shared_anon: 0,
+            pfn_available,
+            huge: 0,
+            thp: 0,
+            ksm: 0,
+            zero_page: 0,
+            shared: 0,
+        }
+    }
+
+    fn add(
+        &mut self,
+        entries: &[PageMapEntry],
+        kpage: Option<&(File, File)>,
+    ) -> Result<(), crate::ProcErr> {
+        self.pages += entries.len();
+        for entry in entries {
+            self.present += entry.present() as usize;
+            self.swapped += entry.swapped() as usize;
+            self.soft_dirty += entry.soft_dirty() as usize;
+            self.exclusive += entry.exclusive() as usize;
+            self.file_or_shared_anon += entry.file_or_shared_anon() as usize;
+        }
+
+        let (flags_file, count_file) = match kpage {
+            Some(files) => files,
+            None => return Ok(()),
+        };
+        let mut pfns: Vec<u64> = entries.iter().filter_map(|e| e.pfn()).collect();
+        pfns.sort_unstable();
+        pfns.dedup();
+        let flags = read_pfn_entries(flags_file, &pfns)?;
+        let counts = read_pfn_entries(count_file, &pfns)?;
+        // a page frame such as the zero page may be mapped by many pages.
+        for pfn in entries.iter().filter_map(|e| e.pfn()) {
+            let i = pfns.binary_search(&pfn).unwrap_or_default();
+            let flags = KPageFlags::from(flags[i]);
+            self.huge += flags.huge() as usize;
+            self.thp += flags.thp() as usize;
+            self.ksm += flags.ksm() as usize;
+            self.zero_page += flags.zero_page() as usize;
+            self.shared += (counts[i] > 1) as usize;
+        }
+        Ok(())
+    }
+}
+
+fn page_summary_impl(path: &str, address: (usize, usize)) -> Result<PageSummary, crate::ProcErr> {
+    let pfn_available = pfn_visible()?;
+    let kpage = if pfn_available {
+        Some((
+            File::open("/proc/kpageflags")?,
+            File::open("/proc/kpagecount")?,
+        ))
+    } else {
+        None
+    };
+    let mut summary = PageSummary::new(pfn_available);
+    match pagemap_chunks(path, address, |entries| {
+        summary.add(entries, kpage.as_ref())
+    }) {
+        Ok(()) => Ok(summary),
+        // regions out of user space like [vsyscall] are not reported by pagemap.
+        Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+            Ok(PageSummary::new(pfn_available))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Return page statistics of a mapping in process `pid`.
+///
+/// Huge page, KSM and sharing statistics require reading /proc/kpageflags
+/// and /proc/kpagecount, which are only readable by root.
+///
+/// An empty summary is returned for regions out of user space like [vsyscall].
+pub fn page_summary_of(pid: Pid, map: &Map) -> Result<PageSummary, crate::ProcErr> {
+    page_summary_impl(&format!("/proc/{}/pagemap", pid), *map.address())
+}
+
+/// Return page statistics of a mapping in current process.
+///
+/// See [`page_summary_of()`](fn.page_summary_of.html) for details.
+pub fn page_summary_self(map: &Map) -> Result<PageSummary, crate::ProcErr> {
+    page_summary_impl("/proc/self/pagemap", *map.address())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_entry() {
+        let entry = PageMapEntry((1 << 63) | (1 << 61) | (1 << 55) | 0x1234);
+        assert!(entry.present());
+        assert!(!entry.swapped());
+        assert!(entry.file_or_shared_anon());
+        assert!(entry.soft_dirty());
+        assert!(!entry.exclusive());
+        assert_eq!(Some(0x1234), entry.pfn());
+        assert_eq!(None, entry.swap_type());
+
+        let entry = PageMapEntry((1 << 62) | (0x56 << 5) | 0x3);
+        assert!(!entry.present());
+        assert_eq!(None, entry.pfn());
+        assert_eq!(Some(3), entry.swap_type());
+        assert_eq!(Some(0x56), entry.swap_offset());
+    }
+
+    #[test]
+    fn test_pagemap_self() {
+        let data = vec![1u8; 4096 * 2];
+        let address = data.as_ptr() as usize;
+        let entries = pagemap_self((address, address + data.len())).unwrap();
+        assert!(entries.len() >= 2);
+        assert!(entries.iter().all(|e| e.present()));
+    }
+
+    #[test]
+    fn test_page_summary_vsyscall() {
+        let map =
+            "ffffffffff600000-ffffffffff601000 --xp 00000000 00:00 0                  [vsyscall]"
+                .parse::<Map>()
+                .unwrap();
+        let summary = page_summary_self(&map).unwrap();
+        assert_eq!(0, summary.pages);
+    }
+
+    #[test]
+    fn test_page_summary_chunks() {
+        let page_size = crate::pid::auxv::page_size().unwrap();
+        let pages = CHUNK_PAGES + 10;
+        let mut data = vec![0u8; page_size * pages];
+        for offset in (0..data.len()).step_by(page_size) {
+            data[offset] = 1;
+        }
+        let start = data.as_ptr() as usize;
+        let end = start + data.len();
+        let map = format!("{:x}-{:x} rw-p 00000000 00:00 0", start, end)
+            .parse::<Map>()
+            .unwrap();
+        let summary = page_summary_self(&map).unwrap();
+        assert!(summary.pages >= pages);
+        assert!(summary.present >= pages);
+        assert_eq!(summary.pages, pagemap_self((start, end)).unwrap().len());
+        if summary.pfn_available {
+            assert_eq!(0, summary.zero_page);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// /proc/[pid]/personality (since Linux 2.6.28)
+// This read-only file exposes the process's execution domain, as
+// set by personality(2).  The value is displayed in hexadecimal
+// notation.
+//
+// Permission to access this file is governed by a ptrace access
+// mode PTRACE_MODE_ATTACH_FSCREDS check; see ptrace(2).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// The lowest byte is the execution domain (PER_LINUX, PER_SVR4, ...),
+// the other bits are the flags defined in include/uapi/linux/personality.h.
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/personality, returned by [`personality_of()`](fn.personality_of.html).
+    pub struct Personality(u32);
+}
+
+impl From<u32> for Personality {
+    fn from(persona: u32) -> Personality {
+        Personality(persona)
+    }
+}
+
+/// The execution domain of Linux, PER_LINUX.
+pub const PER_LINUX: u32 = 0x0000;
+
+impl Personality {
+    /// Return the execution domain, the lowest byte of the personality.
+    pub fn execution_domain(&self) -> u32 {
+        self.0 & 0xff
+    }
+}
+
+macro_rules! flag_methods {
+    ($(
+        $method: ident, $flag: ident, $value: expr
+    );*) => {
+        impl Personality {$(
+            #[doc="Return true if `"]
+            #[doc=stringify!($flag)]
+            #[doc="` is set."]
+            pub fn $method(&self) -> bool {
+                self.0 & $value != 0
+            }
+        )*}
+    };
+}
+
+flag_methods! {
+    uname26, UNAME26, 0x0020000;
+    addr_no_randomize, ADDR_NO_RANDOMIZE, 0x0040000;
+    fdpic_funcptrs, FDPIC_FUNCPTRS, 0x0080000;
+    mmap_page_zero, MMAP_PAGE_ZERO, 0x0100000;
+    addr_compat_layout, ADDR_COMPAT_LAYOUT, 0x0200000;
+    read_implies_exec, READ_IMPLIES_EXEC, 0x0400000;
+    addr_limit_32bit, ADDR_LIMIT_32BIT, 0x0800000;
+    short_inode, SHORT_INODE, 0x1000000;
+    whole_seconds, WHOLE_SECONDS, 0x2000000;
+    sticky_timeouts, STICKY_TIMEOUTS, 0x4000000;
+    addr_limit_3gb, ADDR_LIMIT_3GB, 0x8000000
+}
+
+use std::str::FromStr;
+impl FromStr for Personality {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Personality, crate::ProcErr> {
+        let persona = u32::from_str_radix(s.trim(), 16)?;
+        Ok(Personality(persona))
+    }
+}
+
+pid_instance_impl! {
+    personality_of, "personality", Personality,
+    personality_self, personality_of_of, personality_self_of, personality_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_personality() {
+        let persona = "00440000".parse::<Personality>().unwrap();
+        assert_eq!(PER_LINUX, persona.execution_domain());
+        assert!(persona.addr_no_randomize());
+        assert!(persona.read_implies_exec());
+        assert!(!persona.uname26());
+        assert!(!persona.addr_limit_3gb());
+
+        let persona = "00000010".parse::<Personality>().unwrap();
+        assert_eq!(0x10, persona.execution_domain());
+    }
+}
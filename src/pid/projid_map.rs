@@ -0,0 +1,23 @@
+// /proc/[pid]/projid_map (since Linux 3.7)
+// See user_namespaces(7).
+//
+// Similar to uid_map and gid_map, but for project IDs.  Project
+// IDs are used by the XFS quota mechanism.
+//
+// The format is the same as /proc/[pid]/uid_map, see [`uid_map`](../uid_map/index.html).
+//
+// -- http://man7.org/linux/man-pages/man7/user_namespaces.7.html
+
+use crate::pid::uid_map::write_id_map;
+pub use crate::pid::uid_map::{IdMap, IdRange};
+use crate::pid::Pid;
+
+pid_instance_impl! {
+    projid_map_of, "projid_map", IdMap,
+    projid_map_self, projid_map_of_of, projid_map_self_of, projid_map_self_self
+}
+
+/// Write the project id mapping of process `pid`, which can only be done once for a user namespace.
+pub fn write_projid_map_of(pid: Pid, map: &IdMap) -> Result<(), crate::ProcErr> {
+    write_id_map(&format!("/proc/{}/projid_map", pid), map)
+}
@@ -0,0 +1,178 @@
+// /proc/[pid]/sched
+//
+// This file is only available when the kernel is built with CONFIG_SCHED_DEBUG.
+// It shows the scheduler statistics of a task in a `key : value` table,
+// the set of keys depends on kernel version and configuration:
+//
+//     cat (1249, #threads: 1)
+//     -------------------------------------------------------------------
+//     se.exec_start                                :        271775.158612
+//     se.vruntime                                  :             4.744160
+//     se.sum_exec_runtime                          :             0.046750
+//     se.nr_migrations                             :                    0
+//     nr_switches                                  :                    1
+//     nr_voluntary_switches                        :                    1
+//     nr_involuntary_switches                      :                    0
+//     se.load.weight                               :              1048576
+//     policy                                       :                    0
+//     prio                                         :                  120
+//     clock-delta                                  :                  103
+//     mm->numa_scan_seq                            :                    0
+//     numa_pages_migrated                          :                    0
+//     numa_preferred_nid                           :                   -1
+//     total_numa_faults                            :                    0
+//     current_node=0, numa_group_id=0
+//     numa_faults node=0 task_private=0 task_shared=0 group_private=0 group_shared=0
+//
+// Reference to [`kernel/sched/debug.c`](https://github.com/torvalds/linux/blob/master/kernel/sched/debug.c)
+
+use std::collections::HashMap;
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/sched, returned by [`sched_of()`](fn.sched_of.html).
+    ///
+    /// All `key : value` pairs are kept in `entries` as string,
+    /// since the set of keys changes with kernel configuration.
+    /// Lines which are not `key : value` pairs are kept in `others`.
+    pub struct Sched {
+        comm: String,
+        pid: u32,
+        threads: u32,
+        entries: HashMap<String, String>,
+        others: Vec<String>,
+    }
+}
+
+impl Sched {
+    /// Return the raw value of `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|v| v.as_str())
+    }
+}
+
+macro_rules! get_fields {
+    ($(
+        $method: ident, $key: expr, $type: ty
+    );*) => {
+        impl Sched {$(
+            #[doc="Retrive `"]
+            #[doc=$key]
+            #[doc="`. Return None if it is not exist or can not be parsed."]
+            pub fn $method(&self) -> Option<$type> {
+                self.get($key).and_then(|v| v.parse::<$type>().ok())
+            }
+        )*}
+    };
+}
+
+get_fields! {
+    exec_start, "se.exec_start", f64;
+    vruntime, "se.vruntime", f64;
+    sum_exec_runtime, "se.sum_exec_runtime", f64;
+    nr_migrations, "se.nr_migrations", u64;
+    nr_switches, "nr_switches", u64;
+    nr_voluntary_switches, "nr_voluntary_switches", u64;
+    nr_involuntary_switches, "nr_involuntary_switches", u64;
+    load_weight, "se.load.weight", u64;
+    policy, "policy", u32;
+    prio, "prio", i32;
+    clock_delta, "clock-delta", u64;
+    numa_scan_seq, "mm->numa_scan_seq", u64
+}
+
+use std::str::FromStr;
+impl FromStr for Sched {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Sched, crate::ProcErr> {
+        let mut lines = s.lines();
+
+        // comm may contains spaces and parentheses, so search from the end.
+        let header = lines.next().ok_or("header not found")?;
+        let p1 = header.rfind(" (").ok_or("invalid sched header")?;
+        let comm = header[..p1].to_string();
+        let inner: Vec<&str> = header[p1 + 2..]
+            .trim_end_matches(')')
+            .split(", #threads:")
+            .collect();
+        if inner.len() != 2 {
+            return Err("invalid sched header".into());
+        }
+        let pid = inner[0].trim().parse::<u32>()?;
+        let threads = inner[1].trim().parse::<u32>()?;
+
+        let mut entries = HashMap::new();
+        let mut others = vec![];
+        for line in lines {
+            if line.starts_with("---") || line.trim().is_empty() {
+                continue;
+            }
+            // keys are padded to 45 characters and values to 21, so a long key or value
+            // may have no space around the colon.
+            match line.find(':') {
+                Some(p) if !line[..p].trim().is_empty() => {
+                    entries.insert(
+                        line[..p].trim().to_string(),
+                        line[p + 1..].trim().to_string(),
+                    );
+                }
+                _ => others.push(line.to_string()),
+            }
+        }
+
+        Ok(Sched {
+            comm,
+            pid,
+            threads,
+            entries,
+            others,
+        })
+    }
+}
+
+pid_instance_impl! {
+    sched_of, "sched", Sched,
+    sched_self, sched_of_of, sched_self_of, sched_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_sched() {
+        let source = "tmux: server (1249, #threads: 1)
+-------------------------------------------------------------------
+se.exec_start                                :        271775.158612
+se.vruntime                                  :             4.744160
+nr_switches                                  :                    1
+se.avg.util_est                              :                  182
+prio                                         :                  120
+mm->numa_scan_seq                            :                    0
+se.avg.last_update_time_with_a_very_long_name:                    5
+se.sum_exec_runtime                          :123456789012345678.123456
+current_node=0, numa_group_id=0";
+        let sched = source.parse::<Sched>().unwrap();
+        assert_eq!("tmux: server", sched.comm());
+        assert_eq!(1249, *sched.pid());
+        assert_eq!(1, *sched.threads());
+        assert_eq!(Some(271775.158612), sched.exec_start());
+        assert_eq!(Some(1), sched.nr_switches());
+        assert_eq!(Some(120), sched.prio());
+        assert_eq!(Some(0), sched.numa_scan_seq());
+        assert_eq!(None, sched.clock_delta());
+        assert_eq!(Some("182"), sched.get("se.avg.util_est"));
+        assert_eq!(
+            Some("5"),
+            sched.get("se.avg.last_update_time_with_a_very_long_name")
+        );
+        assert_eq!(
+            Some("123456789012345678.123456"),
+            sched.get("se.sum_exec_runtime")
+        );
+        assert_eq!(
+            &vec!["current_node=0, numa_group_id=0".to_string()],
+            sched.others()
+        );
+    }
+}
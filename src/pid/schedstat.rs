@@ -0,0 +1,70 @@
+// /proc/[pid]/schedstat
+//
+// schedstats also adds a new /proc/<pid>/schedstat file to include some of
+// the same information on a per-process level.  There are three fields in
+// this file correlating for that process to:
+//
+//      1) time spent on the cpu (in nanoseconds)
+//      2) time spent waiting on a runqueue (in nanoseconds)
+//      3) # of timeslices run on this cpu
+//
+// A program could be easily written to make use of these extra fields to
+// report on how well a particular process or set of processes is faring
+// under the scheduler's policies.  A simple version of such a program is
+// available at
+//
+//     http://eaglet.pdxhosts.com/rick/linux/schedstat/v12/latency.c
+//
+// -- https://www.kernel.org/doc/Documentation/scheduler/sched-stats.rst
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/schedstat, returned by [`schedstat_of()`](fn.schedstat_of.html).
+    pub struct SchedStat {
+        /// Time spent on the cpu, in nanoseconds.
+        cpu_time: u64,
+        /// Time spent waiting on a runqueue, in nanoseconds.
+        run_delay: u64,
+        timeslices: u64,
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for SchedStat {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<SchedStat, crate::ProcErr> {
+        let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+        if columns.len() != 3 {
+            return Err("require 3 fields to parse a SchedStat".into());
+        }
+        let cpu_time = columns[0].parse::<u64>()?;
+        let run_delay = columns[1].parse::<u64>()?;
+        let timeslices = columns[2].parse::<u64>()?;
+        Ok(SchedStat {
+            cpu_time,
+            run_delay,
+            timeslices,
+        })
+    }
+}
+
+pid_instance_impl! {
+    schedstat_of, "schedstat", SchedStat,
+    schedstat_self, schedstat_of_of, schedstat_self_of, schedstat_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_schedstat() {
+        let source = "411467 161799 2";
+        let correct = SchedStat {
+            cpu_time: 411467,
+            run_delay: 161799,
+            timeslices: 2,
+        };
+        assert_eq!(correct, source.parse::<SchedStat>().unwrap());
+    }
+}
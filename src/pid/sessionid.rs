@@ -0,0 +1,45 @@
+// /proc/[pid]/sessionid
+//
+// This file holds the audit session id of the process, which is assigned
+// when the login uid is set and is inherited by all of its descendants.
+// 4294967295 means the session id is not set.
+//
+// This file is present only if the kernel was configured with CONFIG_AUDIT.
+// It is not the same as the session id in /proc/[pid]/stat, see setsid(2).
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/sessionid, returned by [`sessionid_of()`](fn.sessionid_of.html).
+    ///
+    /// None means the audit session id is not set.
+    pub struct SessionId(Option<u32>);
+}
+
+use std::str::FromStr;
+impl FromStr for SessionId {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<SessionId, crate::ProcErr> {
+        let id = s.trim().parse::<u32>()?;
+        if id == u32::MAX {
+            Ok(SessionId(None))
+        } else {
+            Ok(SessionId(Some(id)))
+        }
+    }
+}
+
+pid_instance_impl! {
+    sessionid_of, "sessionid", SessionId,
+    sessionid_self, sessionid_of_of, sessionid_self_of, sessionid_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_sessionid() {
+        assert_eq!(SessionId(None), "4294967295".parse::<SessionId>().unwrap());
+        assert_eq!(SessionId(Some(3)), "3".parse::<SessionId>().unwrap());
+    }
+}
@@ -0,0 +1,69 @@
+// /proc/[pid]/setgroups (since Linux 3.19)
+// See user_namespaces(7).
+//
+// The /proc/[pid]/setgroups file displays the string "allow" if
+// processes in the user namespace that contains the process pid
+// are permitted to employ the setgroups(2) system call; it dis‐
+// plays "deny" if setgroups(2) is not permitted in that user
+// namespace.  Note that regardless of the value in the
+// /proc/[pid]/setgroups file (and regardless of the process's
+// capabilities), calls to setgroups(2) are also not permitted if
+// /proc/[pid]/gid_map has not yet been set.
+//
+// A privileged process (one with the CAP_SYS_ADMIN capability in
+// the namespace) may write either of the strings "allow" or
+// "deny" to this file before writing a group ID mapping for this
+// user namespace to the file /proc/[pid]/gid_map.  Writing the
+// string "deny" prevents any process in the user namespace from
+// employing setgroups(2).
+//
+// -- http://man7.org/linux/man-pages/man7/user_namespaces.7.html
+
+use crate::pid::Pid;
+
+/// Represent the content of /proc/[pid]/setgroups, returned by [`setgroups_of()`](fn.setgroups_of.html).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SetGroups {
+    Allow,
+    Deny,
+}
+
+use std::str::FromStr;
+impl FromStr for SetGroups {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<SetGroups, crate::ProcErr> {
+        match s {
+            "allow" => Ok(SetGroups::Allow),
+            "deny" => Ok(SetGroups::Deny),
+            _ => Err(format!("unknow setgroups: {}", s).into()),
+        }
+    }
+}
+
+pid_instance_impl! {
+    setgroups_of, "setgroups", SetGroups,
+    setgroups_self, setgroups_of_of, setgroups_self_of, setgroups_self_self
+}
+
+/// Write /proc/[pid]/setgroups, this must be done before writing /proc/[pid]/gid_map.
+pub fn write_setgroups_of(pid: Pid, value: SetGroups) -> Result<(), crate::ProcErr> {
+    let content = match value {
+        SetGroups::Allow => "allow",
+        SetGroups::Deny => "deny",
+    };
+    std::fs::write(format!("/proc/{}/setgroups", pid), content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_setgroups() {
+        assert_eq!(SetGroups::Allow, "allow".parse::<SetGroups>().unwrap());
+        assert_eq!(SetGroups::Deny, "deny".parse::<SetGroups>().unwrap());
+        assert!("xyz".parse::<SetGroups>().is_err());
+    }
+}
@@ -0,0 +1,123 @@
+// /proc/[pid]/stack (since Linux 2.6.29)
+// This file provides a symbolic trace of the function calls in
+// this process's kernel stack.  This file is provided only if
+// the kernel was built with the CONFIG_STACKTRACE configuration
+// option.
+//
+// Permission to access this file is governed by a ptrace access
+// mode PTRACE_MODE_ATTACH_FSCREDS check; see ptrace(2).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// [<0>] hrtimer_nanosleep+0x7a/0x100
+// [<0>] common_nsleep+0x34/0x70
+// [<0>] __x64_sys_clock_nanosleep+0xd5/0x150
+// [<0>] do_syscall_64+0x70/0x1e0
+// [<0>] entry_SYSCALL_64_after_hwframe+0x76/0x7e
+//
+// Since Linux 4.16, the address is always printed as 0 to avoid leaking kernel addresses.
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/stack, returned by [`stack_of()`](fn.stack_of.html).
+    ///
+    /// The first frame is the innermost one.
+    pub struct Stack(Vec<Frame>);
+}
+
+define_struct! {
+    /// Represent a line in /proc/[pid]/stack.
+    pub struct Frame {
+        /// Always be 0 since Linux 4.16.
+        address: u64,
+        symbol: String,
+        offset: Option<u64>,
+        len: Option<u64>,
+        /// The module which the symbol belongs to, None if it is in the kernel image.
+        module: Option<String>,
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for Frame {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Frame, crate::ProcErr> {
+        let s = s.trim();
+        if !s.starts_with("[<") {
+            return Err("frame should start with [<".into());
+        }
+        let p = s.find(">]").ok_or("invalid frame address")?;
+        let address = u64::from_str_radix(s[2..p].trim_start_matches("0x"), 16)?;
+
+        let mut columns = s[p + 2..].split_ascii_whitespace();
+        let location = columns.next().ok_or("symbol not found")?;
+        let module = columns
+            .next()
+            .map(|m| m.trim_start_matches('[').trim_end_matches(']').to_string());
+
+        let (symbol, offset, len) = if let Some(p) = location.rfind('+') {
+            let ol: Vec<&str> = location[p + 1..].split('/').collect();
+            if ol.len() != 2 {
+                return Err("invalid symbol offset".into());
+            }
+            let offset = u64::from_str_radix(ol[0].trim_start_matches("0x"), 16)?;
+            let len = u64::from_str_radix(ol[1].trim_start_matches("0x"), 16)?;
+            (location[..p].to_string(), Some(offset), Some(len))
+        } else {
+            (location.to_string(), None, None)
+        };
+
+        Ok(Frame {
+            address,
+            symbol,
+            offset,
+            len,
+            module,
+        })
+    }
+}
+
+impl FromStr for Stack {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Stack, crate::ProcErr> {
+        let mut frames = vec![];
+        for line in s.lines() {
+            frames.push(line.parse::<Frame>()?);
+        }
+        Ok(Stack(frames))
+    }
+}
+
+pid_instance_impl! {
+    stack_of, "stack", Stack,
+    stack_self, stack_of_of, stack_self_of, stack_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_frame() {
+        let source = "[<0>] hrtimer_nanosleep+0x7a/0x100";
+        let correct = Frame {
+            address: 0,
+            symbol: "hrtimer_nanosleep".to_string(),
+            offset: Some(0x7a),
+            len: Some(0x100),
+            module: None,
+        };
+        assert_eq!(correct, source.parse::<Frame>().unwrap());
+
+        let source = "[<ffffffffc0a1b2c3>] nfs_wait_bit_killable+0x1f/0x60 [nfs]";
+        let correct = Frame {
+            address: 0xffffffffc0a1b2c3,
+            symbol: "nfs_wait_bit_killable".to_string(),
+            offset: Some(0x1f),
+            len: Some(0x60),
+            module: Some("nfs".to_string()),
+        };
+        assert_eq!(correct, source.parse::<Frame>().unwrap());
+    }
+}
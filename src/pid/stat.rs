@@ -357,12 +357,13 @@ impl FromStr for Stat {
     type Err = crate::ProcErr;
 
     fn from_str(s: &str) -> Result<Stat, crate::ProcErr> {
-        let columns_: Vec<&str> = s.split(|c| c == '(' || c == ')').collect();
-        if columns_.len() != 3 {
-            return Err("no enough fields to parse a Stat".into());
-        }
-        let mut columns: Vec<&str> = vec![columns_[0].trim(), columns_[1].trim()];
-        columns.extend(columns_[2].trim().split_ascii_whitespace());
+        // comm may contains parentheses, e.g. `(sd-pam)`, so search ')' from the end.
+        let (left, right) = match (s.find('('), s.rfind(')')) {
+            (Some(l), Some(r)) if l < r => (l, r),
+            _ => return Err("no enough fields to parse a Stat".into()),
+        };
+        let mut columns: Vec<&str> = vec![s[..left].trim(), &s[left + 1..right]];
+        columns.extend(s[right + 1..].trim().split_ascii_whitespace());
 
         macro_rules! unwrap_integer {
             (
@@ -624,4 +625,13 @@ mod test {
         };
         assert_eq!(correct, source.parse::<Stat>().unwrap());
     }
+
+    #[test]
+    fn test_parse_stat_comm() {
+        let source = "1271 ((sd-pam)) S 1270 1270 1270 0 -1 1077936448 55 0 0 0 0 0 0 0 20 0 1 0 1140 104857600 1143 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 1 0 0 0 0 0 0 0 0 0 0 0 0 0";
+        let stat = source.parse::<Stat>().unwrap();
+        assert_eq!("(sd-pam)", stat.comm);
+        assert_eq!(1270, stat.ppid);
+        assert_eq!('S', stat.state);
+    }
 }
@@ -0,0 +1,111 @@
+// /proc/[pid]/syscall (since Linux 2.6.27)
+// This file exposes the system call number and argument regis‐
+// ters for the system call currently being executed by the
+// process, followed by the values of the stack pointer and pro‐
+// gram counter registers.  The values of all six argument regis‐
+// ters are exposed, although most system calls use fewer regis‐
+// ters.
+//
+// If the process is blocked, but not in a system call, then the
+// file displays -1 in place of the system call number, followed
+// by just the values of the stack pointer and program counter.
+// If process is not blocked, then the file contains just the
+// string "running".
+//
+// This file is present only if the kernel was configured with
+// CONFIG_HAVE_ARCH_TRACEHOOK.
+//
+// Permission to access this file is governed by a ptrace access
+// mode PTRACE_MODE_ATTACH_FSCREDS check; see ptrace(2).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+/// Represent the content of /proc/[pid]/syscall, returned by [`syscall_of()`](fn.syscall_of.html).
+#[derive(Debug, PartialEq, Clone)]
+pub enum Syscall {
+    /// The process is not blocked.
+    Running,
+    /// The process is blocked, but not in a system call.
+    Blocked {
+        stack_pointer: u64,
+        program_counter: u64,
+    },
+    /// The process is blocked in a system call.
+    InSyscall {
+        number: u64,
+        args: [u64; 6],
+        stack_pointer: u64,
+        program_counter: u64,
+    },
+}
+
+fn parse_hex(s: &str) -> Result<u64, crate::ProcErr> {
+    Ok(u64::from_str_radix(s.trim_start_matches("0x"), 16)?)
+}
+
+use std::str::FromStr;
+impl FromStr for Syscall {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Syscall, crate::ProcErr> {
+        let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+        if columns.len() == 1 && columns[0] == "running" {
+            Ok(Syscall::Running)
+        } else if columns.len() == 3 && columns[0] == "-1" {
+            Ok(Syscall::Blocked {
+                stack_pointer: parse_hex(columns[1])?,
+                program_counter: parse_hex(columns[2])?,
+            })
+        } else if columns.len() == 9 {
+            let number = columns[0].parse::<u64>()?;
+            let mut args = [0; 6];
+            for (arg, v) in args.iter_mut().zip(columns[1..7].iter()) {
+                *arg = parse_hex(v)?;
+            }
+            Ok(Syscall::InSyscall {
+                number,
+                args,
+                stack_pointer: parse_hex(columns[7])?,
+                program_counter: parse_hex(columns[8])?,
+            })
+        } else {
+            Err(format!("unknow syscall format: {}", s).into())
+        }
+    }
+}
+
+pid_instance_impl! {
+    syscall_of, "syscall", Syscall,
+    syscall_self, syscall_of_of, syscall_self_of, syscall_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_syscall() {
+        assert_eq!(Syscall::Running, "running".parse::<Syscall>().unwrap());
+
+        let correct = Syscall::Blocked {
+            stack_pointer: 0x7ffe5e5a0e48,
+            program_counter: 0x7f5e4c1a2f4e,
+        };
+        assert_eq!(
+            correct,
+            "-1 0x7ffe5e5a0e48 0x7f5e4c1a2f4e"
+                .parse::<Syscall>()
+                .unwrap()
+        );
+
+        let source =
+            "230 0x0 0x0 0x7fffab6e1fd0 0x7fffab6e2010 0x0 0x0 0x7fffab6e1fb8 0x7fb0ef611503";
+        let correct = Syscall::InSyscall {
+            number: 230,
+            args: [0, 0, 0x7fffab6e1fd0, 0x7fffab6e2010, 0, 0],
+            stack_pointer: 0x7fffab6e1fb8,
+            program_counter: 0x7fb0ef611503,
+        };
+        assert_eq!(correct, source.parse::<Syscall>().unwrap());
+    }
+}
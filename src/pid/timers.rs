@@ -0,0 +1,188 @@
+// /proc/[pid]/timers (since Linux 3.10)
+// A list of the POSIX timers for this process.  Each timer is
+// listed with a line that starts with the string "ID:".  For
+// example:
+//
+//     ID: 1
+//     signal: 60/00007fff86e452a8
+//     notify: signal/pid.2634
+//     ClockID: 0
+//     ID: 0
+//     signal: 60/00007fff86e452a8
+//     notify: signal/pid.2634
+//     ClockID: 1
+//
+// The lines shown for each timer have the following meanings:
+//
+// ID     The ID for this timer.  This is not the same as the timer
+//        ID returned by timer_create(2); rather, it is the same
+//        kernel-internal ID that is available via the si_timerid
+//        field of the siginfo_t structure (see sigaction(2)).
+//
+// signal This is the signal number that this timer uses to deliver
+//        notifications.  This is followed by a slash, and then the
+//        sigev_value value supplied to the signal handler.  Valid
+//        only for timers that notify via a signal.
+//
+// notify The part before the slash specifies the mechanism that
+//        this timer uses to deliver notifications, and is one of
+//        "thread", "signal", or "none".  Immediately following the
+//        slash is either the string "tid" for timers with
+//        SIGEV_THREAD_ID notification, or "pid" for timers that
+//        notify by other mechanisms.  Following the "." is the PID
+//        of the process (or the kernel thread ID of the thread)
+//        that will be delivered a signal if the timer delivers
+//        notifications via a signal.
+//
+// ClockID
+//        This field identifies the clock that the timer uses for
+//        measuring time.  For most clocks, this is a number that
+//        matches one of the user-space CLOCK_* constants exposed
+//        via <time.h>.  CLOCK_PROCESS_CPUTIME_ID timers display
+//        with a value of -6 in this field.  CLOCK_THREAD_CPUTIME_ID
+//        timers display with a value of -2 in this field.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+/// The notification mechanism of a timer.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Notify {
+    Signal,
+    Thread,
+    None,
+    Unknown(String),
+}
+
+/// The receiver of the timer notification.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NotifyTarget {
+    /// The process will be delivered the signal.
+    Pid(i32),
+    /// The thread will be delivered the signal (SIGEV_THREAD_ID).
+    Tid(i32),
+}
+
+define_struct! {
+    /// Represent a POSIX timer in /proc/[pid]/timers.
+    pub struct Timer {
+        id: i32,
+        signal: i32,
+        /// The sigev_value supplied to the signal handler.
+        sigev_value: u64,
+        notify: Notify,
+        notify_target: NotifyTarget,
+        clock_id: i32,
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/timers, returned by [`timers_of()`](fn.timers_of.html).
+    pub struct Timers(Vec<Timer>);
+}
+
+fn field<'a>(line: Option<&'a str>, name: &str) -> Result<&'a str, crate::ProcErr> {
+    let line = line.ok_or(format!("{} not found", name))?;
+    if !line.starts_with(name) {
+        return Err(format!("expect {}, found {}", name, line).into());
+    }
+    Ok(line[name.len()..].trim())
+}
+
+impl Timer {
+    fn from_lines<'a, I>(lines: &mut I) -> Result<Option<Timer>, crate::ProcErr>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let id = match lines.next() {
+            Some(line) => field(Some(line), "ID:")?.parse::<i32>()?,
+            None => return Ok(None),
+        };
+
+        let signal = field(lines.next(), "signal:")?;
+        let mut signal_columns = signal.splitn(2, '/');
+        let signal = signal_columns.next().unwrap_or_default().parse::<i32>()?;
+        let sigev_value = signal_columns.next().ok_or("sigev_value not found")?;
+        let sigev_value = u64::from_str_radix(sigev_value, 16)?;
+
+        let notify = field(lines.next(), "notify:")?;
+        let mut notify_columns = notify.splitn(2, '/');
+        let notify = match notify_columns.next().unwrap_or_default() {
+            "signal" => Notify::Signal,
+            "thread" => Notify::Thread,
+            "none" => Notify::None,
+            other => Notify::Unknown(other.to_string()),
+        };
+        let target = notify_columns.next().ok_or("notify target not found")?;
+        let notify_target = if let Some(tid) = target.strip_prefix("tid.") {
+            NotifyTarget::Tid(tid.parse::<i32>()?)
+        } else if let Some(pid) = target.strip_prefix("pid.") {
+            NotifyTarget::Pid(pid.parse::<i32>()?)
+        } else {
+            return Err(format!("unknow notify target: {}", target).into());
+        };
+
+        let clock_id = field(lines.next(), "ClockID:")?.parse::<i32>()?;
+
+        Ok(Some(Timer {
+            id,
+            signal,
+            sigev_value,
+            notify,
+            notify_target,
+            clock_id,
+        }))
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for Timers {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Timers, crate::ProcErr> {
+        let mut lines = s.lines().filter(|l| !l.trim().is_empty());
+        let mut timers = vec![];
+        while let Some(timer) = Timer::from_lines(&mut lines)? {
+            timers.push(timer);
+        }
+        Ok(Timers(timers))
+    }
+}
+
+pid_instance_impl! {
+    timers_of, "timers", Timers,
+    timers_self, timers_of_of, timers_self_of, timers_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_timers() {
+        let source = "ID: 1
+signal: 60/00007fff86e452a8
+notify: signal/pid.2634
+ClockID: 0
+ID: 0
+signal: 14/0000000000000000
+notify: thread/tid.2635
+ClockID: -6";
+        let timers = source.parse::<Timers>().unwrap();
+        assert_eq!(2, timers.len());
+        let t0 = Timer {
+            id: 1,
+            signal: 60,
+            sigev_value: 0x7fff86e452a8,
+            notify: Notify::Signal,
+            notify_target: NotifyTarget::Pid(2634),
+            clock_id: 0,
+        };
+        assert_eq!(t0, timers[0]);
+        assert_eq!(&Notify::Thread, timers[1].notify());
+        assert_eq!(&NotifyTarget::Tid(2635), timers[1].notify_target());
+        assert_eq!(-6, *timers[1].clock_id());
+
+        assert!("".parse::<Timers>().unwrap().is_empty());
+        assert!("ID: 1\nsignal: 60/0".parse::<Timers>().is_err());
+    }
+}
@@ -0,0 +1,84 @@
+// /proc/[pid]/timerslack_ns (since Linux 4.6)
+// This file exposes the process's "current" timer slack value,
+// expressed in nanoseconds.  The file is writable, allowing the
+// process's timer slack value to be changed.  Writing 0 to this
+// file resets the "current" timer slack to the "default" timer
+// slack value.  For further details, see the discussion of
+// PR_SET_TIMERSLACK in prctl(2).
+//
+// Initially, permission to access this file was governed by a
+// ptrace access mode PTRACE_MODE_ATTACH_FSCREDS check (see
+// ptrace(2)).  However, this was subsequently deemed too strict a
+// requirement (and had the side effect that requiring a process
+// to have the CAP_SYS_PTRACE capability would also allow it to
+// view and change any process's memory).  Therefore, since Linux
+// 4.9, only the (weaker) CAP_SYS_NICE capability is required to
+// access this file.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::pid::Pid;
+
+// Report Unsupported instead of NotFound on kernels without the file.
+fn check_err(e: std::io::Error) -> crate::ProcErr {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        if let Err(crate::ProcErr::Unsupported(msg)) =
+            crate::proc::version::require_at_least(4, 6, "/proc/[pid]/timerslack_ns")
+        {
+            return crate::ProcErr::Unsupported(msg);
+        }
+    }
+    e.into()
+}
+
+fn timerslack_ns_impl(path: &str) -> Result<u64, crate::ProcErr> {
+    let content = std::fs::read_to_string(path).map_err(check_err)?;
+    Ok(content.trim().parse::<u64>()?)
+}
+
+fn set_timerslack_ns_impl(path: &str, ns: u64) -> Result<(), crate::ProcErr> {
+    std::fs::write(path, ns.to_string()).map_err(check_err)?;
+    Ok(())
+}
+
+/// Return the current timer slack of process `pid`, in nanoseconds.
+pub fn timerslack_ns_of(pid: Pid) -> Result<u64, crate::ProcErr> {
+    timerslack_ns_impl(&format!("/proc/{}/timerslack_ns", pid))
+}
+
+/// Return the current timer slack of current process, in nanoseconds.
+pub fn timerslack_ns_self() -> Result<u64, crate::ProcErr> {
+    timerslack_ns_impl("/proc/self/timerslack_ns")
+}
+
+/// Set the timer slack of process `pid`, in nanoseconds.
+///
+/// Writing 0 resets it to the default timer slack of the process.
+pub fn set_timerslack_ns_of(pid: Pid, ns: u64) -> Result<(), crate::ProcErr> {
+    set_timerslack_ns_impl(&format!("/proc/{}/timerslack_ns", pid), ns)
+}
+
+/// Set the timer slack of current process, in nanoseconds.
+///
+/// Writing 0 resets it to the default timer slack of the process.
+pub fn set_timerslack_ns_self(ns: u64) -> Result<(), crate::ProcErr> {
+    set_timerslack_ns_impl("/proc/self/timerslack_ns", ns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timerslack_ns() {
+        let origin = match timerslack_ns_self() {
+            Ok(ns) => ns,
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(crate::ProcErr::Unsupported(_)) => return,
+            Err(e) => panic!("{:?}", e),
+        };
+        if let Err(e) = set_timerslack_ns_self(origin) {
+            assert!(e.is_permission_denied(), "{:?}", e);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// /proc/[pid]/uid_map, /proc/[pid]/gid_map (since Linux 3.5)
+// See user_namespaces(7).
+//
+// Each line in the file specifies a 1-to-1 mapping of a range of
+// contiguous user (group) IDs between two user namespaces.  The
+// specification in each line takes the form of three numbers
+// delimited by white space.  The first two numbers specify the
+// starting user ID in each of the two user namespaces.  The third
+// number specifies the length of the mapped range.  In detail,
+// the fields are interpreted as follows:
+//
+// (1) The start of the range of IDs in the user namespace of the
+//     process pid.
+//
+// (2) The start of the range of IDs to which the IDs specified by
+//     field one map.  How field two is interpreted depends on
+//     whether the process that opened uid_map and the process pid
+//     are in the same user namespace, as follows:
+//
+//     a) If the two processes are in different user namespaces:
+//        field two is the start of a range of IDs in the user
+//        namespace of the process that opened uid_map.
+//
+//     b) If the two processes are in the same user namespace:
+//        field two is the start of the range of IDs in the parent
+//        user namespace of the process pid.  This case enables
+//        the opener of uid_map (the common case here is opening
+//        /proc/self/uid_map) to see the mapping of user IDs into
+//        the user namespace of the process that created this user
+//        namespace.
+//
+// (3) The length of the range of user IDs that is mapped between
+//     the two user namespaces.
+//
+// After the creation of a new user namespace, the uid_map file of
+// one of the processes in the namespace may be written to once to
+// define the mapping of user IDs in the new user namespace.  An
+// attempt to write more than once to a uid_map file in a user
+// namespace fails with the error EPERM.  Similar rules apply for
+// gid_map files.
+//
+// The lines written to uid_map (gid_map) must conform to the fol‐
+// lowing rules:
+//
+// *  The three fields must be valid numbers, and the last field
+//    must be greater than 0.
+//
+// *  Lines are terminated by newline characters.
+//
+// *  There is a limit on the number of lines in the file.  In
+//    Linux 4.14 and earlier, this limit was (arbitrarily) set at
+//    5 lines.  Since Linux 4.15, the limit is 340 lines.
+//
+// *  The range of user IDs (group IDs) specified in each line
+//    cannot overlap with the ranges in any other lines.
+//
+// *  At least one line must be written to the file.
+//
+// Writes that violate the above rules fail with the error EINVAL.
+//
+// -- http://man7.org/linux/man-pages/man7/user_namespaces.7.html
+
+use crate::pid::Pid;
+
+define_struct! {
+    /// Represent a line in /proc/[pid]/uid_map, /proc/[pid]/gid_map and /proc/[pid]/projid_map.
+    pub struct IdRange {
+        /// The start of the range in the user namespace of the process.
+        inside: u32,
+        /// The start of the range in the user namespace of the opener,
+        /// or the parent user namespace if the opener is in the same user namespace.
+        outside: u32,
+        length: u32,
+    }
+}
+
+impl IdRange {
+    pub fn new(inside: u32, outside: u32, length: u32) -> IdRange {
+        IdRange {
+            inside,
+            outside,
+            length,
+        }
+    }
+
+    /// Return the id outside the namespace if `id` is in this range.
+    pub fn to_outside(&self, id: u32) -> Option<u32> {
+        let offset = id.checked_sub(self.inside)?;
+        if offset < self.length {
+            Some(self.outside + offset)
+        } else {
+            None
+        }
+    }
+
+    /// Return the id inside the namespace if `id` is in this range.
+    pub fn to_inside(&self, id: u32) -> Option<u32> {
+        let offset = id.checked_sub(self.outside)?;
+        if offset < self.length {
+            Some(self.inside + offset)
+        } else {
+            None
+        }
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for IdRange {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<IdRange, crate::ProcErr> {
+        let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+        if columns.len() != 3 {
+            return Err("require 3 fields to parse an IdRange".into());
+        }
+        let inside = columns[0].parse::<u32>()?;
+        let outside = columns[1].parse::<u32>()?;
+        let length = columns[2].parse::<u32>()?;
+        Ok(IdRange {
+            inside,
+            outside,
+            length,
+        })
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/uid_map, returned by [`uid_map_of()`](fn.uid_map_of.html).
+    ///
+    /// An empty IdMap means no mapping has been written yet.
+    pub struct IdMap(Vec<IdRange>);
+}
+
+impl IdMap {
+    pub fn new(ranges: Vec<IdRange>) -> IdMap {
+        IdMap(ranges)
+    }
+
+    /// Translate `id` in the user namespace of the process to the outside one.
+    ///
+    /// Return None if it is not mapped.
+    pub fn to_outside(&self, id: u32) -> Option<u32> {
+        self.0.iter().find_map(|r| r.to_outside(id))
+    }
+
+    /// Translate `id` outside to the one in the user namespace of the process.
+    ///
+    /// Return None if it is not mapped.
+    pub fn to_inside(&self, id: u32) -> Option<u32> {
+        self.0.iter().find_map(|r| r.to_inside(id))
+    }
+}
+
+impl FromStr for IdMap {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<IdMap, crate::ProcErr> {
+        let mut ranges = vec![];
+        for line in s.lines() {
+            ranges.push(line.parse::<IdRange>()?);
+        }
+        Ok(IdMap(ranges))
+    }
+}
+
+impl std::fmt::Display for IdMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for r in self.0.iter() {
+            writeln!(f, "{} {} {}", r.inside, r.outside, r.length)?;
+        }
+        Ok(())
+    }
+}
+
+/// The mapping must be written by a single write(2), or the kernel rejects the rest.
+pub(crate) fn write_id_map(path: &str, map: &IdMap) -> Result<(), crate::ProcErr> {
+    use std::io::Write;
+    let content = map.to_string();
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let n = file.write(content.as_bytes())?;
+    if n != content.len() {
+        return Err(format!(
+            "only {} of {} bytes are written to {}",
+            n,
+            content.len(),
+            path
+        )
+        .into());
+    }
+    Ok(())
+}
+
+pid_instance_impl! {
+    uid_map_of, "uid_map", IdMap,
+    uid_map_self, uid_map_of_of, uid_map_self_of, uid_map_self_self
+}
+
+/// Write the user id mapping of process `pid`, which can only be done once for a user namespace.
+///
+/// For a freshly unshared child, the writer should have CAP_SETUID in the parent user namespace,
+/// or only map its own effective uid.
+pub fn write_uid_map_of(pid: Pid, map: &IdMap) -> Result<(), crate::ProcErr> {
+    write_id_map(&format!("/proc/{}/uid_map", pid), map)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_id_map() {
+        let source = "         0       1000          1\n         1     100000      65536";
+        let correct = IdMap(vec![
+            IdRange::new(0, 1000, 1),
+            IdRange::new(1, 100000, 65536),
+        ]);
+        let map = source.parse::<IdMap>().unwrap();
+        assert_eq!(correct, map);
+        assert_eq!("0 1000 1\n1 100000 65536\n", map.to_string());
+    }
+
+    #[test]
+    fn test_translate() {
+        let map = IdMap(vec![
+            IdRange::new(0, 1000, 1),
+            IdRange::new(1, 100000, 65536),
+        ]);
+        assert_eq!(Some(1000), map.to_outside(0));
+        assert_eq!(Some(100009), map.to_outside(10));
+        assert_eq!(None, map.to_outside(65537));
+        assert_eq!(Some(0), map.to_inside(1000));
+        assert_eq!(Some(65536), map.to_inside(165535));
+        assert_eq!(None, map.to_inside(1001));
+    }
+}
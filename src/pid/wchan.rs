@@ -0,0 +1,48 @@
+// /proc/[pid]/wchan (since Linux 2.6.0)
+// The symbolic name corresponding to the location in the kernel
+// where the process is sleeping.
+//
+// Permission to access this file is governed by a ptrace access
+// mode PTRACE_MODE_READ_FSCREDS check; see ptrace(2).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+define_struct! {
+    /// Represent the content of /proc/[pid]/wchan, returned by [`wchan_of()`](fn.wchan_of.html).
+    ///
+    /// None means the process is not sleeping in the kernel (the file contains `0`).
+    pub struct Wchan(Option<String>);
+}
+
+use std::str::FromStr;
+impl FromStr for Wchan {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Wchan, crate::ProcErr> {
+        let s = s.trim();
+        if s.is_empty() || s == "0" {
+            Ok(Wchan(None))
+        } else {
+            Ok(Wchan(Some(s.to_string())))
+        }
+    }
+}
+
+pid_instance_impl! {
+    wchan_of, "wchan", Wchan,
+    wchan_self, wchan_of_of, wchan_self_of, wchan_self_self
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_wchan() {
+        assert_eq!(Wchan(None), "0".parse::<Wchan>().unwrap());
+        assert_eq!(
+            Wchan(Some("hrtimer_nanosleep".to_string())),
+            "hrtimer_nanosleep".parse::<Wchan>().unwrap()
+        );
+    }
+}
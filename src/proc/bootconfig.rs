@@ -0,0 +1,159 @@
+// /proc/bootconfig
+// /proc/bootconfig is a user-space interface of the boot config.
+// Unlike /proc/cmdline, this file shows the key-value style list.
+// Each key-value pair is shown in each line with following style::
+//
+//     KEY[.WORDS...] = "[VALUE]"[,"VALUE2"...]
+//
+// -- https://www.kernel.org/doc/Documentation/admin-guide/bootconfig.rst
+//
+// The kernel parameters in the `kernel` subtree are appended to the kernel command
+// line, and the `init` subtree is passed to init. A value containing a double quote
+// is quoted with single quotes. Since Linux 5.19 the command line given by the
+// bootloader follows as comments:
+//
+//     # Parameters from bootloader:
+//     # root=/dev/sda1 ro
+
+define_struct! {
+    /// A key and its values in /proc/bootconfig.
+    pub struct BootConfigEntry {
+        key: String,
+        /// An array value has more than one element, an empty value is `[""]`.
+        values: Vec<String>,
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/bootconfig, returned by [`bootconfig()`](fn.bootconfig.html).
+    pub struct BootConfig {
+        entries: Vec<BootConfigEntry>,
+        /// The parameters from the bootloader, if they are shown.
+        bootloader: Option<String>,
+    }
+}
+
+impl BootConfig {
+    /// Return the values of `key`, e.g. `kernel.console`.
+    pub fn get(&self, key: &str) -> Option<&[String]> {
+        self.entries
+            .iter()
+            .find(|e| e.key == key)
+            .map(|e| e.values.as_slice())
+    }
+
+    /// Return the entries under `prefix`, with the prefix removed from the keys,
+    /// e.g. `subtree("kernel")` returns the parameters appended to the kernel command line.
+    pub fn subtree(&self, prefix: &str) -> Vec<BootConfigEntry> {
+        let prefix = format!("{}.", prefix);
+        self.entries
+            .iter()
+            .filter_map(|e| {
+                e.key.strip_prefix(&prefix).map(|k| BootConfigEntry {
+                    key: k.to_string(),
+                    values: e.values.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+// `"a", "b"` or `'say "hi"'`
+fn parse_values(s: &str) -> Result<Vec<String>, crate::ProcErr> {
+    let mut ret = vec![];
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let quote = rest.chars().next().unwrap_or_default();
+        if quote != '"' && quote != '\'' {
+            return Err(format!("unquoted bootconfig value: {}", rest).into());
+        }
+        let end = rest[1..]
+            .find(quote)
+            .ok_or("unterminated bootconfig value")?
+            + 1;
+        ret.push(rest[1..end].to_string());
+        rest = rest[end + 1..].trim_start();
+        if let Some(r) = rest.strip_prefix(',') {
+            rest = r.trim_start();
+        } else if !rest.is_empty() {
+            return Err(format!("invalid bootconfig value: {}", s).into());
+        }
+    }
+    Ok(ret)
+}
+
+use std::str::FromStr;
+impl FromStr for BootConfig {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<BootConfig, crate::ProcErr> {
+        let mut entries = vec![];
+        let mut bootloader = None;
+        let mut lines = s.lines();
+        while let Some(line) = lines.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.starts_with('#') {
+                if line.starts_with("# Parameters from bootloader:") {
+                    bootloader = lines
+                        .next()
+                        .and_then(|l| l.strip_prefix('#'))
+                        .map(|l| l.trim().to_string());
+                }
+                continue;
+            }
+            let mut kv = line.splitn(2, '=');
+            let key = kv.next().unwrap_or_default().trim().to_string();
+            let values = parse_values(kv.next().ok_or("bootconfig value not found")?)?;
+            entries.push(BootConfigEntry { key, values });
+        }
+        Ok(BootConfig {
+            entries,
+            bootloader,
+        })
+    }
+}
+
+instance_impl! {
+    bootconfig, "/proc/bootconfig", BootConfig
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_bootconfig() {
+        let source = "kernel.console = \"ttyS0,115200\"
+kernel.quiet = \"\"
+kernel.ftrace.event.filter = 'pid < 128 && comm == \"sh\"'
+init.systemd.unit = \"rescue.target\"
+kernel.module.options = \"a\", \"b c\"
+# Parameters from bootloader:
+# root=/dev/sda1 ro
+";
+        let config = source.parse::<BootConfig>().unwrap();
+        assert_eq!(5, config.entries().len());
+        assert_eq!(
+            Some(&["ttyS0,115200".to_string()][..]),
+            config.get("kernel.console")
+        );
+        assert_eq!(Some(&["".to_string()][..]), config.get("kernel.quiet"));
+        assert_eq!(
+            "pid < 128 && comm == \"sh\"",
+            config.get("kernel.ftrace.event.filter").unwrap()[0]
+        );
+        assert_eq!(
+            &vec!["a".to_string(), "b c".to_string()],
+            config.entries()[4].values()
+        );
+        assert_eq!(&Some("root=/dev/sda1 ro".to_string()), config.bootloader());
+
+        let init = config.subtree("init");
+        assert_eq!(1, init.len());
+        assert_eq!("systemd.unit", init[0].key());
+
+        assert!("kernel.x = unquoted".parse::<BootConfig>().is_err());
+    }
+}
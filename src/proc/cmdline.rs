@@ -1,16 +1,254 @@
+// /proc/cmdline
+// Arguments passed to the Linux kernel at boot time.  Often done
+// via a boot manager such as lilo(8) or grub(8).
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// The kernel parses the command line into parameters as `param[=value]`,
+// separated by spaces. A value containing spaces can be quoted with double
+// quotes, e.g. `param="spaces in here"`. Parameters of modules are in the form
+// of `module.param=value`, and `-` and `_` are equivalent in parameter names.
+// Everything after `--` is passed to init.
+//
+// -- https://www.kernel.org/doc/Documentation/admin-guide/kernel-parameters.rst
+
+define_struct! {
+    /// A parameter of the kernel command line.
+    pub struct Param {
+        /// The module name of `module.param=value`, None for a core parameter.
+        module: Option<String>,
+        key: String,
+        /// None for a flag such as `quiet`.
+        value: Option<String>,
+    }
+}
+
+impl Param {
+    /// Return the name with the module, e.g. `random.trust_cpu`.
+    pub fn name(&self) -> String {
+        match self.module {
+            Some(ref module) => format!("{}.{}", module, self.key),
+            None => self.key.clone(),
+        }
+    }
+
+    fn is(&self, name: &str) -> bool {
+        let normalize = |s: &str| s.replace('-', "_");
+        normalize(&self.name()) == normalize(name)
+    }
+}
+
 define_struct! {
     /// Represent the content of /proc/cmdline, returned by [`cmdline()`](fn.cmdline.html)
-    pub struct Cmdline(String);
+    pub struct Cmdline {
+        raw: String,
+        params: Vec<Param>,
+        /// Arguments after `--`, which are passed to init.
+        init_args: Vec<String>,
+    }
+}
+
+impl Cmdline {
+    /// Return the value of parameter `name`, the last one wins if it is repeated.
+    ///
+    /// `name` may contain the module, e.g. `random.trust_cpu`.
+    /// Return None if the parameter is not exist or is a flag.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .rev()
+            .find(|p| p.is(name))
+            .and_then(|p| p.value.as_deref())
+    }
+
+    /// Return all values of the repeated parameter `name`, e.g. `console`.
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.params
+            .iter()
+            .filter(|p| p.is(name))
+            .filter_map(|p| p.value.as_deref())
+            .collect()
+    }
+
+    /// Return true if parameter `name` is present, with or without a value.
+    pub fn contains(&self, name: &str) -> bool {
+        self.params.iter().any(|p| p.is(name))
+    }
+
+    /// Return the names of parameters without value, e.g. `quiet`.
+    pub fn flags(&self) -> Vec<String> {
+        self.params
+            .iter()
+            .filter(|p| p.value.is_none())
+            .map(|p| p.name())
+            .collect()
+    }
+
+    /// Return the parameters of `module`.
+    pub fn module_params(&self, module: &str) -> Vec<&Param> {
+        self.params
+            .iter()
+            .filter(|p| p.module.as_deref() == Some(module))
+            .collect()
+    }
+}
+
+// Split the first argument from `args`, return the param, the value and the rest.
+// Quotes are handled as next_arg() of the kernel, only a quote at the beginning of
+// the argument or the value and the matching quote at the end are removed.
+//
+// Reference to [`lib/cmdline.c`](https://github.com/torvalds/linux/blob/master/lib/cmdline.c)
+fn next_arg(args: &str) -> (&str, Option<&str>, &str) {
+    let mut args = args;
+    let mut in_quote = false;
+    let mut quoted = false;
+    if let Some(rest) = args.strip_prefix('"') {
+        args = rest;
+        in_quote = true;
+        quoted = true;
+    }
+
+    let bytes = args.as_bytes();
+    let mut i = 0;
+    let mut equals = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() && !in_quote {
+            break;
+        }
+        if equals == 0 && bytes[i] == b'=' {
+            equals = i;
+        }
+        if bytes[i] == b'"' {
+            in_quote = !in_quote;
+        }
+        i += 1;
+    }
+
+    let ends_with_quote = i > 0 && bytes[i - 1] == b'"';
+    let mut end = i;
+    let mut value_start = None;
+    if equals != 0 {
+        let mut start = equals + 1;
+        // don't include quotes in value.
+        if bytes.get(start) == Some(&b'"') {
+            start += 1;
+            if ends_with_quote {
+                end = i - 1;
+            }
+        }
+        value_start = Some(start);
+    }
+    if quoted && ends_with_quote {
+        end = i - 1;
+    }
+
+    let (param, value) = match value_start {
+        Some(start) => (&args[..equals], Some(&args[start.min(end)..end])),
+        None => (&args[..end], None),
+    };
+    let rest = if i < bytes.len() { &args[i + 1..] } else { "" };
+    (param, value, rest.trim_start())
 }
 
 use std::str::FromStr;
+impl FromStr for Param {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Param, crate::ProcErr> {
+        let mut kv = s.splitn(2, '=');
+        let name = kv.next().unwrap_or_default();
+        Param::new(name, kv.next())
+    }
+}
+
+impl Param {
+    fn new(name: &str, value: Option<&str>) -> Result<Param, crate::ProcErr> {
+        if name.is_empty() {
+            return Err("empty parameter name".into());
+        }
+        let value = value.map(|v| v.to_string());
+        let (module, key) = match name.find('.') {
+            Some(p) => (Some(name[..p].to_string()), name[p + 1..].to_string()),
+            None => (None, name.to_string()),
+        };
+        Ok(Param { module, key, value })
+    }
+}
+
 impl FromStr for Cmdline {
     type Err = crate::ProcErr;
 
     fn from_str(s: &str) -> Result<Cmdline, crate::ProcErr> {
-        let cmdline = s.trim().to_string();
-        Ok(Cmdline(cmdline))
+        let raw = s.trim().to_string();
+        let mut params = vec![];
+        let mut init_args = vec![];
+        let mut args = raw.as_str();
+        let mut after_dashes = false;
+        while !args.is_empty() {
+            let (param, value, rest) = next_arg(args);
+            args = rest;
+            if after_dashes {
+                match value {
+                    Some(value) => init_args.push(format!("{}={}", param, value)),
+                    None => init_args.push(param.to_string()),
+                }
+            } else if param == "--" && value.is_none() {
+                after_dashes = true;
+            } else {
+                params.push(Param::new(param, value)?);
+            }
+        }
+        Ok(Cmdline {
+            raw,
+            params,
+            init_args,
+        })
     }
 }
 
 instance_impl!(cmdline, "/proc/cmdline", Cmdline);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cmdline() {
+        let source = "BOOT_IMAGE=/vmlinuz-6.1 root=UUID=0a1b ro console=tty0 console=ttyS0,115200n8 quiet random.trust_cpu=1 dyndbg=\"file drivers/usb/* +p\" \"acpi_osi=!Windows 2012\" log-buf-len=1M -- single --addr 0.0.0.0\n";
+        let cmdline = source.parse::<Cmdline>().unwrap();
+        assert_eq!(source.trim(), cmdline.raw());
+        assert_eq!(Some("UUID=0a1b"), cmdline.get("root"));
+        assert_eq!(Some("ttyS0,115200n8"), cmdline.get("console"));
+        assert_eq!(vec!["tty0", "ttyS0,115200n8"], cmdline.get_all("console"));
+        assert_eq!(Some("file drivers/usb/* +p"), cmdline.get("dyndbg"));
+        assert_eq!(Some("!Windows 2012"), cmdline.get("acpi_osi"));
+        assert_eq!(Some("1M"), cmdline.get("log_buf_len"));
+        assert_eq!(None, cmdline.get("quiet"));
+        assert!(cmdline.contains("quiet"));
+        assert!(!cmdline.contains("single"));
+        assert_eq!(vec!["ro", "quiet"], cmdline.flags());
+
+        assert_eq!(Some("1"), cmdline.get("random.trust_cpu"));
+        let random = cmdline.module_params("random");
+        assert_eq!(1, random.len());
+        assert_eq!("trust_cpu", random[0].key());
+
+        assert_eq!(&vec!["single", "--addr", "0.0.0.0"], cmdline.init_args());
+    }
+
+    #[test]
+    fn test_next_arg() {
+        assert_eq!(("a\"b c\"d", None, "e"), next_arg("a\"b c\"d e"));
+        assert_eq!(("foo", Some("bar\"baz"), ""), next_arg("foo=\"bar\"baz"));
+        assert_eq!(
+            ("foo", Some("bar baz"), "x"),
+            next_arg("foo=\"bar baz\"  x")
+        );
+        assert_eq!(("foo", Some("a b"), ""), next_arg("\"foo=a b\""));
+        assert_eq!(("foo", Some(""), ""), next_arg("foo=\""));
+
+        let cmdline = "a\"b c\"d foo=\"bar\"baz".parse::<Cmdline>().unwrap();
+        assert!(cmdline.contains("a\"b c\"d"));
+        assert_eq!(Some("bar\"baz"), cmdline.get("foo"));
+    }
+}
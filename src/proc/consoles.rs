@@ -1,15 +1,135 @@
+// /proc/consoles
+// This file lists the registered system console devices, for example:
+//
+//     tty0                 -WU (ECp  )    4:7
+//     ttyS0                -W- (EC  p a)    4:64
+//
+// The columns are the device name, the operations (R for read, W for write
+// and U for unblank), the flags and the major:minor of the device.
+// The flags are:
+//
+//     E  enabled
+//     C  preferred console
+//     B  primary boot console
+//     p  used for printk buffer
+//     b  not a TTY but a Braille device
+//     a  safe to use when cpu is offline
+//
+// -- https://www.kernel.org/doc/Documentation/filesystems/proc.rst
+
+define_struct! {
+    /// A console device in /proc/consoles.
+    pub struct Console {
+        name: String,
+        read: bool,
+        write: bool,
+        unblank: bool,
+        enabled: bool,
+        preferred: bool,
+        boot: bool,
+        printk_buffer: bool,
+        braille: bool,
+        anytime: bool,
+        /// The major and minor number of the device, None if there is no tty device.
+        device: Option<(u32, u32)>,
+    }
+}
+
 define_struct! {
     /// Represent the content of /proc/consoles, returned by [`consoles()`](fn.consoles.html)
-    pub struct Consoles(String);
+    pub struct Consoles(Vec<Console>);
+}
+
+impl Consoles {
+    /// Return the preferred console, which is /dev/console.
+    pub fn preferred(&self) -> Option<&Console> {
+        self.0.iter().find(|c| c.preferred)
+    }
 }
 
 use std::str::FromStr;
+impl FromStr for Console {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Console, crate::ProcErr> {
+        let left = s.find('(').ok_or("console flags not found")?;
+        let right = s.rfind(')').ok_or("console flags not found")?;
+        let columns: Vec<&str> = s[..left].split_ascii_whitespace().collect();
+        if columns.len() != 2 || columns[1].len() != 3 {
+            return Err(format!("unknow console line: {}", s).into());
+        }
+        let ops = columns[1];
+        let flags = &s[left + 1..right];
+
+        let device = s[right + 1..].trim();
+        let device = if device.is_empty() {
+            None
+        } else {
+            let mut nums = device.splitn(2, ':');
+            let major = nums.next().unwrap_or_default().parse::<u32>()?;
+            let minor = nums.next().ok_or("minor not found")?.parse::<u32>()?;
+            Some((major, minor))
+        };
+
+        Ok(Console {
+            name: columns[0].to_string(),
+            read: ops.contains('R'),
+            write: ops.contains('W'),
+            unblank: ops.contains('U'),
+            enabled: flags.contains('E'),
+            preferred: flags.contains('C'),
+            boot: flags.contains('B'),
+            printk_buffer: flags.contains('p'),
+            braille: flags.contains('b'),
+            anytime: flags.contains('a'),
+            device,
+        })
+    }
+}
+
 impl FromStr for Consoles {
     type Err = crate::ProcErr;
 
     fn from_str(s: &str) -> Result<Consoles, crate::ProcErr> {
-        Ok(Consoles(s.to_string()))
+        let mut ret = vec![];
+        for line in s.lines() {
+            if !line.trim().is_empty() {
+                ret.push(line.parse::<Console>()?);
+            }
+        }
+        Ok(Consoles(ret))
     }
 }
 
 instance_impl!(consoles, "/proc/consoles", Consoles);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_consoles() {
+        let source = "tty0                 -WU (E p  )    4:7
+ttyS0                -W- (EC  p a)    4:64
+netcon0              -W- (E      )";
+        let consoles = source.parse::<Consoles>().unwrap();
+        assert_eq!(3, consoles.len());
+        let correct = Console {
+            name: "ttyS0".to_string(),
+            read: false,
+            write: true,
+            unblank: false,
+            enabled: true,
+            preferred: true,
+            boot: false,
+            printk_buffer: true,
+            braille: false,
+            anytime: true,
+            device: Some((4, 64)),
+        };
+        assert_eq!(Some(&correct), consoles.preferred());
+        assert!(consoles[0].unblank);
+        assert!(!consoles[0].preferred);
+        assert_eq!(None, consoles[2].device);
+    }
+}
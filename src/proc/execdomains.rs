@@ -7,18 +7,87 @@
 // -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s1-proc-topfiles#idm139745909615040
 
 define_struct! {
-    pub struct ExecDomains(String);
+    /// An execution domain in /proc/execdomains.
+    pub struct ExecDomain {
+        /// The first personality of the range.
+        start: u32,
+        /// The last personality of the range.
+        end: u32,
+        name: String,
+        /// The module providing the domain, `kernel` for built-in domains.
+        module: String,
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/execdomains, returned by [`execdomains()`](fn.execdomains.html).
+    pub struct ExecDomains(Vec<ExecDomain>);
 }
 
 use std::str::FromStr;
+impl FromStr for ExecDomain {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<ExecDomain, crate::ProcErr> {
+        let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+        if columns.len() < 3 {
+            return Err("require 3 fields to parse an ExecDomain".into());
+        }
+        let mut range = columns[0].splitn(2, '-');
+        let start = range.next().unwrap_or_default().parse::<u32>()?;
+        let end = range
+            .next()
+            .ok_or("invalid personality range")?
+            .parse::<u32>()?;
+        // the name may contain spaces.
+        let name = columns[1..columns.len() - 1].join(" ");
+        let module = columns[columns.len() - 1]
+            .trim_matches(['[', ']'])
+            .to_string();
+        Ok(ExecDomain {
+            start,
+            end,
+            name,
+            module,
+        })
+    }
+}
+
 impl FromStr for ExecDomains {
     type Err = crate::ProcErr;
 
     fn from_str(s: &str) -> Result<ExecDomains, crate::ProcErr> {
-        Ok(ExecDomains(s.to_string()))
+        let mut ret = vec![];
+        for line in s.lines() {
+            if !line.trim().is_empty() {
+                ret.push(line.parse::<ExecDomain>()?);
+            }
+        }
+        Ok(ExecDomains(ret))
     }
 }
 
 instance_impl! {
     execdomains, "/proc/execdomains", ExecDomains
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_execdomains() {
+        let source = "0-0\tLinux           \t[kernel]
+1-1\tSVR4 personality\t[svr4]";
+        let domains = source.parse::<ExecDomains>().unwrap();
+        let correct = ExecDomain {
+            start: 0,
+            end: 0,
+            name: "Linux".to_string(),
+            module: "kernel".to_string(),
+        };
+        assert_eq!(correct, domains[0]);
+        assert_eq!("SVR4 personality", domains[1].name());
+        assert_eq!("svr4", domains[1].module());
+    }
+}
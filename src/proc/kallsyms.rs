@@ -0,0 +1,263 @@
+// /proc/kallsyms (since Linux 2.5.71)
+// This holds the kernel exported symbol definitions used by the
+// modules(X) tools to dynamically link and bind loadable modules.
+// In Linux 2.5.47 and earlier, a similar file with slightly
+// different syntax was named ksyms.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+//
+// Each line is the address, the type as nm(1), the name and the module of the symbol:
+//
+//     ffffffff81000000 T _text
+//     ffffffffc0a01000 t nfs_init_server_aclclient	[nfs]
+//
+// When /proc/sys/kernel/kptr_restrict is set, or for users without CAP_SYSLOG
+// when it is 1, all addresses are shown as 0.
+//
+// The percpu symbols are absolute (`A`), their addresses are offsets in the percpu area:
+//
+//     0000000000001000 A cpu_number
+//
+// The core kernel ends at `_end`, and a module occupies the memory size at the offset
+// shown in /proc/modules.
+
+use std::collections::BTreeMap;
+
+define_struct! {
+    /// Represent a line of /proc/kallsyms, returned by [`kallsyms()`](fn.kallsyms.html).
+    pub struct Symbol {
+        /// 0 if the address is hidden by kptr_restrict.
+        address: u64,
+        /// The symbol type as nm(1), e.g. `T` for global text, `d` for local data.
+        symbol_type: char,
+        name: String,
+        /// None for a symbol of the core kernel.
+        module: Option<String>,
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for Symbol {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Symbol, crate::ProcErr> {
+        let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+        if columns.len() != 3 && columns.len() != 4 {
+            return Err("require 3 or 4 fields to parse a Symbol".into());
+        }
+        let address = u64::from_str_radix(columns[0], 16)?;
+        let symbol_type = columns[1].chars().next().ok_or("symbol type not found")?;
+        let module = columns
+            .get(3)
+            .map(|m| m.trim_matches(['[', ']']).to_string());
+        Ok(Symbol {
+            address,
+            symbol_type,
+            name: columns[2].to_string(),
+            module,
+        })
+    }
+}
+
+list_impl! {
+    kallsyms, "/proc/kallsyms", Symbol, '\n', 0
+}
+
+/// Symbols sorted by address for resolving kernel addresses, returned by [`symbol_table()`](fn.symbol_table.html).
+#[derive(Debug, PartialEq, Clone)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    restricted: bool,
+    /// The end of the core kernel, `_end` or `_etext`.
+    core_end: Option<u64>,
+    /// The start and end of each module.
+    modules: BTreeMap<String, (u64, u64)>,
+}
+
+impl SymbolTable {
+    /// Build a table from `symbols`, symbols with address 0 and absolute symbols
+    /// such as percpu variables can not be resolved.
+    ///
+    /// The addresses of module symbols are not bounded until the modules are
+    /// given by [`with_modules()`](#method.with_modules).
+    pub fn from_symbols(symbols: Vec<Symbol>) -> SymbolTable {
+        let restricted = !symbols.is_empty() && symbols.iter().all(|s| s.address == 0);
+        let mut symbols: Vec<Symbol> = symbols
+            .into_iter()
+            .filter(|s| s.address != 0 && !['A', 'a'].contains(&s.symbol_type))
+            .collect();
+        symbols.sort_by_key(|s| s.address);
+        let core_symbol = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| s.module.is_none() && s.name == name)
+                .map(|s| s.address)
+        };
+        let core_end = core_symbol("_end").or_else(|| core_symbol("_etext"));
+        SymbolTable {
+            symbols,
+            restricted,
+            core_end,
+            modules: BTreeMap::new(),
+        }
+    }
+
+    /// Bound the addresses of module symbols by the memory of `modules`,
+    /// usually read from [`modules()`](../modules/fn.modules.html).
+    pub fn with_modules(mut self, modules: &[crate::proc::modules::Module]) -> SymbolTable {
+        for module in modules.iter().filter(|m| *m.offset() != 0) {
+            let start = *module.offset() as u64;
+            let end = start + *module.mem_size() as u64;
+            self.modules.insert(module.name().to_string(), (start, end));
+        }
+        self
+    }
+
+    /// Return true if all addresses are hidden by kptr_restrict,
+    /// then no address can be resolved.
+    pub fn is_restricted(&self) -> bool {
+        self.restricted
+    }
+
+    /// Return the symbols with a known address, sorted by address.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Return the symbol named `name`.
+    pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|s| s.name == name)
+    }
+
+    /// Return the symbol containing `address` and the offset of `address` in the symbol,
+    /// which is the closest symbol at or below `address`.
+    ///
+    /// Return None if `address` is beyond the end of the core kernel or the module
+    /// of the closest symbol.
+    pub fn resolve(&self, address: u64) -> Option<(&Symbol, u64)> {
+        let mut index = self.symbols.partition_point(|s| s.address <= address);
+        if index == 0 {
+            return None;
+        }
+        index -= 1;
+        // prefer the first one of the symbols at the same address.
+        let found = self.symbols[index].address;
+        while index > 0 && self.symbols[index - 1].address == found {
+            index -= 1;
+        }
+        let symbol = &self.symbols[index];
+        let in_range = match symbol.module {
+            None => match self.core_end {
+                Some(end) => address < end,
+                None => true,
+            },
+            Some(ref module) => match self.modules.get(module) {
+                Some(&(start, end)) => start <= address && address < end,
+                None => true,
+            },
+        };
+        if in_range {
+            Some((symbol, address - symbol.address))
+        } else {
+            None
+        }
+    }
+
+    /// Format `address` as `symbol+0xoffset`, followed by ` [module]` for a module symbol.
+    pub fn symbolize(&self, address: u64) -> Option<String> {
+        let (symbol, offset) = self.resolve(address)?;
+        let mut ret = format!("{}+{:#x}", symbol.name, offset);
+        if let Some(ref module) = symbol.module {
+            ret.push_str(&format!(" [{}]", module));
+        }
+        Some(ret)
+    }
+}
+
+/// Return the [`SymbolTable`](struct.SymbolTable.html) of /proc/kallsyms,
+/// module symbols are bounded by /proc/modules.
+pub fn symbol_table() -> Result<SymbolTable, crate::ProcErr> {
+    let table = SymbolTable::from_symbols(kallsyms()?);
+    match crate::proc::modules::modules() {
+        Ok(modules) => Ok(table.with_modules(&modules)),
+        // the kernel is built without CONFIG_MODULES.
+        Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(table),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_symbol() {
+        let correct = Symbol {
+            address: 0xffffffffc0a01000,
+            symbol_type: 't',
+            name: "nfs_init_server_aclclient".to_string(),
+            module: Some("nfs".to_string()),
+        };
+        let source = "ffffffffc0a01000 t nfs_init_server_aclclient\t[nfs]";
+        assert_eq!(correct, source.parse::<Symbol>().unwrap());
+
+        let symbol = "0000000000000000 T _text".parse::<Symbol>().unwrap();
+        assert_eq!(0, symbol.address);
+        assert_eq!(None, symbol.module);
+    }
+
+    #[test]
+    fn test_symbol_table() {
+        let source = "ffffffff81000000 T _stext
+ffffffff81000000 T _text
+ffffffff81001000 T do_one_initcall
+0000000000000000 A fixed_percpu_data
+0000000000001000 A cpu_number
+ffffffff81002040 t schedule
+ffffffff82000000 B _end
+ffffffffc0a01000 t nfs_init_server_aclclient\t[nfs]";
+        let symbols: Vec<Symbol> = source.lines().map(|l| l.parse().unwrap()).collect();
+        let modules = vec!["nfs 4096 0 - Live 0xffffffffc0a01000"
+            .parse::<crate::proc::modules::Module>()
+            .unwrap()];
+        let table = SymbolTable::from_symbols(symbols).with_modules(&modules);
+        assert!(!table.is_restricted());
+        assert_eq!(6, table.symbols().len());
+
+        let (symbol, offset) = table.resolve(0xffffffff81001010).unwrap();
+        assert_eq!("do_one_initcall", symbol.name());
+        assert_eq!(0x10, offset);
+        assert_eq!(
+            "_stext",
+            table.resolve(0xffffffff81000008).unwrap().0.name()
+        );
+        assert_eq!(None, table.resolve(0x1000));
+        assert_eq!(None, table.resolve(0x1008));
+        // the gap between the core kernel and modules, and beyond the last module.
+        assert_eq!(None, table.resolve(0xffffffffa0000000));
+        assert_eq!(None, table.symbolize(0xffffffffc0a02000));
+        assert_eq!(None, table.symbolize(0xffffffffffff0000));
+        assert_eq!(
+            Some("schedule+0x0".to_string()),
+            table.symbolize(0xffffffff81002040)
+        );
+        assert_eq!(
+            Some("nfs_init_server_aclclient+0x2a [nfs]".to_string()),
+            table.symbolize(0xffffffffc0a0102a)
+        );
+        assert_eq!(
+            Some(0xffffffff81001000),
+            table.lookup("do_one_initcall").map(|s| s.address)
+        );
+    }
+
+    #[test]
+    fn test_symbol_table_restricted() {
+        let source = "0000000000000000 T _text
+0000000000000000 T schedule";
+        let symbols: Vec<Symbol> = source.lines().map(|l| l.parse().unwrap()).collect();
+        let table = SymbolTable::from_symbols(symbols);
+        assert!(table.is_restricted());
+        assert_eq!(None, table.resolve(0xffffffff81000000));
+    }
+}
@@ -0,0 +1,28 @@
+// /proc/kpagecount (since Linux 2.6.25)
+// This file contains a 64-bit count of the number of times each
+// physical page frame is mapped, indexed by page frame number
+// (see the discussion of /proc/[pid]/pagemap).
+//
+// The /proc/kpagecount file is present only if the CON‐
+// FIG_PROC_PAGE_MONITOR kernel configuration option is enabled.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use crate::proc::kpageflags::read_u64_entries;
+use std::fs::File;
+
+/// Return the number of times page frame `pfn` is mapped, read from /proc/kpagecount.
+///
+/// This file is only readable by root.
+pub fn kpagecount(pfn: u64) -> Result<u64, crate::ProcErr> {
+    let file = File::open("/proc/kpagecount")?;
+    Ok(read_u64_entries(&file, pfn, 1)?[0])
+}
+
+/// Return the map counts of `count` page frames start from `pfn` in /proc/kpagecount.
+///
+/// This file is only readable by root.
+pub fn kpagecount_range(pfn: u64, count: usize) -> Result<Vec<u64>, crate::ProcErr> {
+    let file = File::open("/proc/kpagecount")?;
+    read_u64_entries(&file, pfn, count)
+}
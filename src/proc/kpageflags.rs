@@ -0,0 +1,152 @@
+// /proc/kpageflags (since Linux 2.6.25)
+// This file contains 64-bit masks corresponding to each physical
+// page frame; it is indexed by page frame number (see the dis‐
+// cussion of /proc/[pid]/pagemap).  The bits are as follows:
+//
+//     0 - KPF_LOCKED
+//     1 - KPF_ERROR
+//     2 - KPF_REFERENCED
+//     3 - KPF_UPTODATE
+//     4 - KPF_DIRTY
+//     5 - KPF_LRU
+//     6 - KPF_ACTIVE
+//     7 - KPF_SLAB
+//     8 - KPF_WRITEBACK
+//     9 - KPF_RECLAIM
+//    10 - KPF_BUDDY
+//    11 - KPF_MMAP           (since Linux 2.6.31)
+//    12 - KPF_ANON           (since Linux 2.6.31)
+//    13 - KPF_SWAPCACHE      (since Linux 2.6.31)
+//    14 - KPF_SWAPBACKED     (since Linux 2.6.31)
+//    15 - KPF_COMPOUND_HEAD  (since Linux 2.6.31)
+//    16 - KPF_COMPOUND_TAIL  (since Linux 2.6.31)
+//    17 - KPF_HUGE           (since Linux 2.6.31)
+//    18 - KPF_UNEVICTABLE    (since Linux 2.6.31)
+//    19 - KPF_HWPOISON       (since Linux 2.6.31)
+//    20 - KPF_NOPAGE         (since Linux 2.6.31)
+//    21 - KPF_KSM            (since Linux 2.6.32)
+//    22 - KPF_THP            (since Linux 3.4)
+//    23 - KPF_OFFLINE        (since Linux 5.0)
+//    24 - KPF_ZERO_PAGE      (since Linux 4.0)
+//    25 - KPF_IDLE           (since Linux 4.3)
+//    26 - KPF_PGTABLE        (since Linux 4.18)
+//
+// For further details on the meanings of these bits, see the
+// kernel source file Documentation/admin-guide/mm/pagemap.rst.
+// Before kernel 2.6.29, KPF_WRITEBACK, KPF_RECLAIM, KPF_BUDDY,
+// and KPF_LOCKED did not report correctly.
+//
+// The /proc/kpageflags file is present only if the CON‐
+// FIG_PROC_PAGE_MONITOR kernel configuration option is enabled.
+//
+// -- http://man7.org/linux/man-pages/man5/proc.5.html
+
+use std::fs::File;
+use std::os::unix::fs::FileExt;
+
+define_struct! {
+    /// Represent an entry of /proc/kpageflags, returned by [`kpageflags()`](fn.kpageflags.html).
+    pub struct KPageFlags(u64);
+}
+
+impl From<u64> for KPageFlags {
+    fn from(flags: u64) -> KPageFlags {
+        KPageFlags(flags)
+    }
+}
+
+macro_rules! flag_methods {
+    ($(
+        $method: ident, $bit: expr
+    );*) => {
+        impl KPageFlags {$(
+            #[doc="Return true if bit "]
+            #[doc=stringify!($bit)]
+            #[doc=" is set."]
+            pub fn $method(&self) -> bool {
+                self.0 & (1 << $bit) != 0
+            }
+        )*}
+    };
+}
+
+flag_methods! {
+    locked, 0;
+    error, 1;
+    referenced, 2;
+    uptodate, 3;
+    dirty, 4;
+    lru, 5;
+    active, 6;
+    slab, 7;
+    writeback, 8;
+    reclaim, 9;
+    buddy, 10;
+    mmap, 11;
+    anon, 12;
+    swapcache, 13;
+    swapbacked, 14;
+    compound_head, 15;
+    compound_tail, 16;
+    huge, 17;
+    unevictable, 18;
+    hwpoison, 19;
+    nopage, 20;
+    ksm, 21;
+    thp, 22;
+    offline, 23;
+    zero_page, 24;
+    idle, 25;
+    pgtable, 26
+}
+
+/// Read `count` native u64 entries start from `index`.
+///
+/// This is used by files which are arrays of u64, such as /proc/kpageflags and /proc/[pid]/pagemap.
+pub(crate) fn read_u64_entries(
+    file: &File,
+    index: u64,
+    count: usize,
+) -> Result<Vec<u64>, crate::ProcErr> {
+    let mut buf = vec![0; count * 8];
+    file.read_exact_at(&mut buf, index * 8)?;
+    let mut ret = Vec::with_capacity(count);
+    for chunk in buf.chunks(8) {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(chunk);
+        ret.push(u64::from_ne_bytes(bytes));
+    }
+    Ok(ret)
+}
+
+/// Return the flags of page frame `pfn` in /proc/kpageflags.
+///
+/// This file is only readable by root.
+pub fn kpageflags(pfn: u64) -> Result<KPageFlags, crate::ProcErr> {
+    let file = File::open("/proc/kpageflags")?;
+    Ok(KPageFlags(read_u64_entries(&file, pfn, 1)?[0]))
+}
+
+/// Return the flags of `count` page frames start from `pfn` in /proc/kpageflags.
+///
+/// This file is only readable by root.
+pub fn kpageflags_range(pfn: u64, count: usize) -> Result<Vec<KPageFlags>, crate::ProcErr> {
+    let file = File::open("/proc/kpageflags")?;
+    let entries = read_u64_entries(&file, pfn, count)?;
+    Ok(entries.into_iter().map(KPageFlags).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_flags() {
+        let flags = KPageFlags((1 << 21) | (1 << 17) | 1);
+        assert!(flags.locked());
+        assert!(flags.huge());
+        assert!(flags.ksm());
+        assert!(!flags.thp());
+        assert!(!flags.anon());
+    }
+}
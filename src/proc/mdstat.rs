@@ -12,19 +12,486 @@
 // -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s1-proc-topfiles#s2-proc-mdstat
 //
 
+// A md array with its members, geometry and sync progress looks like:
+//
+//     Personalities : [raid1] [raid6] [raid5] [raid4]
+//     md0 : active raid5 sdd1[4] sdc1[2] sdb1[1] sda1[0](F)
+//           5860270080 blocks super 1.2 level 5, 512k chunk, algorithm 2 [4/3] [UUU_]
+//           [===>.................]  recovery = 17.2% (336040448/1953423360) finish=152.2min speed=177068K/sec
+//           bitmap: 0/15 pages [0KB], 65536KB chunk
+//
+//     unused devices: <none>
+//
+// The number in brackets after a member is its descriptor number (`rdev->desc_nr`) in the
+// superblock, which is not the slot of the disk in the array once a disk has been replaced
+// or re-added. It is followed by (W) for write-mostly, (J) for journal, (F) for faulty,
+// (S) for spare and (R) for replacement.
+// `[4/3]` is the number of raid disks and working disks, `[UUU_]` shows each raid disk
+// in slot order, U for in sync and _ for failed or missing.
+//
+// The slot of a member is in /sys/block/<md>/md/dev-<member>/slot, `none` if the member
+// is not an active disk of the array.
+//
+// Reference to [`drivers/md/md.c`](https://github.com/torvalds/linux/blob/master/drivers/md/md.c)
+
+define_struct! {
+    /// Represent the content of /proc/mdstat, returned by [`mdstat()`](fn.mdstat.html).
+    pub struct MdStat {
+        /// Loaded personalities, e.g. raid1, raid5.
+        personalities: Vec<String>,
+        arrays: Vec<MdArray>,
+        unused_devices: Vec<String>,
+    }
+}
+
+/// The state of a md array.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ArrayState {
+    Active,
+    ReadOnly,
+    /// Read-only until the first write.
+    AutoReadOnly,
+    Inactive,
+}
+
+define_struct! {
+    /// A member device of a md array.
+    pub struct MdMember {
+        device: String,
+        /// The descriptor number in the superblock, not the slot in the array.
+        desc_nr: u32,
+        write_mostly: bool,
+        journal: bool,
+        faulty: bool,
+        spare: bool,
+        replacement: bool,
+    }
+}
+
+/// The sync action of a md array.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SyncAction {
+    Resync,
+    Recovery,
+    Reshape,
+    Check,
+    Repair,
+    Unknown(String),
+}
+
+impl From<&str> for SyncAction {
+    fn from(s: &str) -> SyncAction {
+        match s {
+            "resync" => SyncAction::Resync,
+            "recovery" => SyncAction::Recovery,
+            "reshape" => SyncAction::Reshape,
+            "check" => SyncAction::Check,
+            "repair" => SyncAction::Repair,
+            _ => SyncAction::Unknown(s.to_string()),
+        }
+    }
+}
+
+define_struct! {
+    /// A running sync action.
+    pub struct SyncProgress {
+        action: SyncAction,
+        percent: f64,
+        /// Completed blocks, in KB.
+        done: u64,
+        /// Total blocks, in KB.
+        total: u64,
+        /// Estimated time to finish, in minutes.
+        finish_minutes: Option<f64>,
+        /// Speed in KB/sec.
+        speed_kb: Option<u64>,
+    }
+}
+
+/// The sync status of a md array.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SyncStatus {
+    Running(SyncProgress),
+    /// Waiting for another array sharing the same devices.
+    Delayed(SyncAction),
+    /// Waiting for the array to be writable.
+    Pending(SyncAction),
+}
+
 define_struct! {
-    pub struct MdStat(String);
+    /// A md array in /proc/mdstat.
+    pub struct MdArray {
+        name: String,
+        state: ArrayState,
+        /// None for an inactive array.
+        personality: Option<String>,
+        members: Vec<MdMember>,
+        /// Size of the array, in 1K blocks.
+        blocks: Option<u64>,
+        /// The superblock version, None for 0.90 superblocks.
+        super_version: Option<String>,
+        /// Chunk size in KB.
+        chunk_kb: Option<u64>,
+        raid_disks: Option<u32>,
+        working_disks: Option<u32>,
+        /// The status of each raid disk in slot order, true for in sync.
+        health: Vec<bool>,
+        sync: Option<SyncStatus>,
+        /// The `bitmap:` line.
+        bitmap: Option<String>,
+    }
+}
+
+impl MdArray {
+    /// Return true if some raid disks are failed or missing.
+    pub fn is_degraded(&self) -> bool {
+        self.health.iter().any(|h| !h)
+    }
+
+    /// Return the slot of `member` in the array, read from /sys/block/[md]/md/dev-[member]/slot.
+    ///
+    /// None if the member is not an active disk of the array, e.g. a spare.
+    pub fn slot_of(&self, member: &MdMember) -> Result<Option<u32>, crate::ProcErr> {
+        let path = format!("/sys/block/{}/md/dev-{}/slot", self.name, member.device);
+        parse_slot(&std::fs::read_to_string(path)?)
+    }
+
+    /// Return the member in `slot`.
+    pub fn member_of_slot(&self, slot: u32) -> Result<Option<&MdMember>, crate::ProcErr> {
+        for member in self.members.iter() {
+            if self.slot_of(member)? == Some(slot) {
+                return Ok(Some(member));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return true if `member` is in sync, None if it is not an active disk of the array.
+    pub fn is_in_sync(&self, member: &MdMember) -> Result<Option<bool>, crate::ProcErr> {
+        match self.slot_of(member)? {
+            Some(slot) => Ok(self.health.get(slot as usize).cloned()),
+            None => Ok(None),
+        }
+    }
+}
+
+fn parse_slot(s: &str) -> Result<Option<u32>, crate::ProcErr> {
+    match s.trim() {
+        "none" => Ok(None),
+        slot => Ok(Some(slot.parse::<u32>()?)),
+    }
 }
 
 use std::str::FromStr;
+impl FromStr for MdMember {
+    type Err = crate::ProcErr;
+
+    // `sda1[0](F)(W)`
+    fn from_str(s: &str) -> Result<MdMember, crate::ProcErr> {
+        let left = s.find('[').ok_or("member descriptor number not found")?;
+        let right = s.find(']').ok_or("member descriptor number not found")?;
+        let desc_nr = s
+            .get(left + 1..right)
+            .ok_or("invalid member")?
+            .parse::<u32>()?;
+        let flags = &s[right + 1..];
+        Ok(MdMember {
+            device: s[..left].to_string(),
+            desc_nr,
+            write_mostly: flags.contains("(W)"),
+            journal: flags.contains("(J)"),
+            faulty: flags.contains("(F)"),
+            spare: flags.contains("(S)"),
+            replacement: flags.contains("(R)"),
+        })
+    }
+}
+
+// `[===>....]  recovery = 17.2% (336040448/1953423360) finish=152.2min speed=177068K/sec`
+// or `resync=DELAYED`
+fn parse_sync(line: &str) -> Result<Option<SyncStatus>, crate::ProcErr> {
+    let line = match line.rfind(']') {
+        Some(p) if line.starts_with('[') => &line[p + 1..],
+        _ => line,
+    };
+    let mut kv = line.splitn(2, '=');
+    let action = SyncAction::from(kv.next().unwrap_or_default().trim());
+    let mut columns = match kv.next() {
+        Some(v) => v.split_ascii_whitespace(),
+        None => return Ok(None),
+    };
+    let value = columns.next().unwrap_or_default();
+    match value {
+        "DELAYED" => return Ok(Some(SyncStatus::Delayed(action))),
+        "PENDING" => return Ok(Some(SyncStatus::Pending(action))),
+        _ => {}
+    }
+
+    let percent = value.trim_end_matches('%').parse::<f64>()?;
+    let mut done = 0;
+    let mut total = 0;
+    let mut finish_minutes = None;
+    let mut speed_kb = None;
+    for column in columns {
+        if let Some(v) = column.strip_prefix("finish=") {
+            finish_minutes = Some(v.trim_end_matches("min").parse::<f64>()?);
+        } else if let Some(v) = column.strip_prefix("speed=") {
+            speed_kb = Some(v.trim_end_matches("K/sec").parse::<u64>()?);
+        } else if column.starts_with('(') {
+            let mut blocks = column.trim_matches(['(', ')']).splitn(2, '/');
+            done = blocks.next().unwrap_or_default().parse::<u64>()?;
+            total = blocks
+                .next()
+                .ok_or("total blocks not found")?
+                .parse::<u64>()?;
+        }
+    }
+    Ok(Some(SyncStatus::Running(SyncProgress {
+        action,
+        percent,
+        done,
+        total,
+        finish_minutes,
+        speed_kb,
+    })))
+}
+
+impl MdArray {
+    fn from_lines(header: &str, lines: &[&str]) -> Result<MdArray, crate::ProcErr> {
+        let mut columns = header.splitn(2, " : ");
+        let name = columns.next().unwrap_or_default().trim().to_string();
+        let mut columns = columns
+            .next()
+            .ok_or("no enough fields to parse a MdArray")?
+            .split_ascii_whitespace()
+            .peekable();
+
+        let mut state = match columns.next() {
+            Some("active") => ArrayState::Active,
+            Some("inactive") => ArrayState::Inactive,
+            other => return Err(format!("unknow md array state: {:?}", other).into()),
+        };
+        match columns.peek() {
+            Some(&"(read-only)") => state = ArrayState::ReadOnly,
+            Some(&"(auto-read-only)") => state = ArrayState::AutoReadOnly,
+            _ => {}
+        }
+        if state != ArrayState::Active && state != ArrayState::Inactive {
+            columns.next();
+        }
+        let personality = match columns.peek() {
+            Some(c) if !c.contains('[') => columns.next().map(|p| p.to_string()),
+            _ => None,
+        };
+        let mut members = vec![];
+        for column in columns {
+            members.push(column.parse::<MdMember>()?);
+        }
+
+        let mut blocks = None;
+        let mut super_version = None;
+        let mut chunk_kb = None;
+        let mut raid_disks = None;
+        let mut working_disks = None;
+        let mut health = vec![];
+        let mut sync = None;
+        let mut bitmap = None;
+        for line in lines {
+            let line = line.trim();
+            if let Some(v) = line.strip_prefix("bitmap:") {
+                bitmap = Some(v.trim().to_string());
+                continue;
+            }
+            if line.contains('=') {
+                sync = parse_sync(line)?;
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split_ascii_whitespace().collect();
+            if columns.get(1) != Some(&"blocks") {
+                continue;
+            }
+            blocks = Some(columns[0].parse::<u64>()?);
+            for (i, column) in columns.iter().enumerate() {
+                let next = columns.get(i + 1).cloned().unwrap_or_default();
+                if *column == "super" {
+                    super_version = Some(next.to_string());
+                } else if next.starts_with("chunk") {
+                    let chunk = column.trim_end_matches(['k', 'K']);
+                    chunk_kb = Some(chunk.parse::<u64>()?);
+                } else if column.starts_with('[') && column.contains('/') {
+                    let mut disks = column.trim_matches(['[', ']']).splitn(2, '/');
+                    raid_disks = Some(disks.next().unwrap_or_default().parse::<u32>()?);
+                    working_disks = Some(disks.next().unwrap_or_default().parse::<u32>()?);
+                } else if column.starts_with('[') {
+                    health = column
+                        .trim_matches(['[', ']'])
+                        .chars()
+                        .map(|c| c == 'U')
+                        .collect();
+                }
+            }
+        }
+
+        Ok(MdArray {
+            name,
+            state,
+            personality,
+            members,
+            blocks,
+            super_version,
+            chunk_kb,
+            raid_disks,
+            working_disks,
+            health,
+            sync,
+            bitmap,
+        })
+    }
+}
+
 impl FromStr for MdStat {
     type Err = crate::ProcErr;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(MdStat(s.to_string()))
+        let mut personalities = vec![];
+        let mut arrays = vec![];
+        let mut unused_devices = vec![];
+
+        let lines: Vec<&str> = s.lines().collect();
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            i += 1;
+            if let Some(v) = line.strip_prefix("Personalities :") {
+                personalities = v
+                    .split_ascii_whitespace()
+                    .map(|p| p.trim_matches(['[', ']']).to_string())
+                    .collect();
+            } else if let Some(v) = line.strip_prefix("unused devices:") {
+                unused_devices = v
+                    .split_ascii_whitespace()
+                    .filter(|d| *d != "<none>")
+                    .map(|d| d.to_string())
+                    .collect();
+            } else if line.starts_with("md") && line.contains(" : ") {
+                let start = i;
+                while i < lines.len() && lines[i].starts_with(|c: char| c.is_ascii_whitespace()) {
+                    i += 1;
+                }
+                arrays.push(MdArray::from_lines(line, &lines[start..i])?);
+            }
+        }
+
+        Ok(MdStat {
+            personalities,
+            arrays,
+            unused_devices,
+        })
     }
 }
 
 instance_impl! {
     mdstat, "/proc/mdstat", MdStat
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_mdstat() {
+        let source = "Personalities : [raid1] [raid6] [raid5] [raid4] [raid10]
+md0 : active raid5 sdd1[4] sdc1[2] sdb1[1] sda1[0](F) sde1[5](S)
+      5860270080 blocks super 1.2 level 5, 512k chunk, algorithm 2 [4/3] [UUU_]
+      [===>.................]  recovery = 17.2% (336040448/1953423360) finish=152.2min speed=177068K/sec
+      bitmap: 0/15 pages [0KB], 65536KB chunk
+
+md1 : active (auto-read-only) raid1 sdb2[1](W) sda2[0]
+      1953378304 blocks [2/2] [UU]
+      \tresync=PENDING
+
+md2 : inactive sdb3[1](S) sda3[0](S)
+      2093056 blocks super 1.2
+
+md3 : active raid10 sdf[3] sde[2] sdd[1] sdc[0]
+      209584128 blocks super 1.2 512K chunks 2 near-copies [4/4] [UUUU]
+      [=>...................]  check =  5.1% (10752/209584128) finish=32.1min speed=108544K/sec
+
+unused devices: <none>";
+        let stat = source.parse::<MdStat>().unwrap();
+        assert_eq!(
+            &vec!["raid1", "raid6", "raid5", "raid4", "raid10"],
+            stat.personalities()
+        );
+        assert!(stat.unused_devices().is_empty());
+        assert_eq!(4, stat.arrays().len());
+
+        let md0 = &stat.arrays()[0];
+        assert_eq!("md0", md0.name());
+        assert_eq!(&ArrayState::Active, md0.state());
+        assert_eq!(&Some("raid5".to_string()), md0.personality());
+        assert_eq!(5, md0.members().len());
+        assert!(md0.members()[3].faulty);
+        assert!(md0.members()[4].spare);
+        assert_eq!(2, md0.members()[1].desc_nr);
+        assert_eq!(Some(5860270080), md0.blocks);
+        assert_eq!(Some("1.2".to_string()), md0.super_version);
+        assert_eq!(Some(512), md0.chunk_kb);
+        assert_eq!((Some(4), Some(3)), (md0.raid_disks, md0.working_disks));
+        assert_eq!(vec![true, true, true, false], md0.health);
+        assert!(md0.is_degraded());
+        let progress = SyncProgress {
+            action: SyncAction::Recovery,
+            percent: 17.2,
+            done: 336040448,
+            total: 1953423360,
+            finish_minutes: Some(152.2),
+            speed_kb: Some(177068),
+        };
+        assert_eq!(Some(SyncStatus::Running(progress)), md0.sync);
+        assert_eq!(
+            Some("0/15 pages [0KB], 65536KB chunk".to_string()),
+            md0.bitmap
+        );
+
+        let md1 = &stat.arrays()[1];
+        assert_eq!(&ArrayState::AutoReadOnly, md1.state());
+        assert_eq!(&Some("raid1".to_string()), md1.personality());
+        assert!(md1.members()[0].write_mostly);
+        assert_eq!(None, md1.super_version);
+        assert_eq!(Some(SyncStatus::Pending(SyncAction::Resync)), md1.sync);
+        assert!(!md1.is_degraded());
+
+        let md2 = &stat.arrays()[2];
+        assert_eq!(&ArrayState::Inactive, md2.state());
+        assert_eq!(&None, md2.personality());
+        assert_eq!(2, md2.members().len());
+        assert_eq!(Some(2093056), md2.blocks);
+
+        let md3 = &stat.arrays()[3];
+        assert_eq!(Some(512), md3.chunk_kb);
+        match md3.sync() {
+            Some(SyncStatus::Running(p)) => {
+                assert_eq!(&SyncAction::Check, p.action());
+                assert_eq!(5.1, *p.percent());
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_slot() {
+        assert_eq!(Some(3), parse_slot("3\n").unwrap());
+        assert_eq!(None, parse_slot("none\n").unwrap());
+    }
+
+    #[test]
+    fn test_parse_mdstat_empty() {
+        let source = "Personalities :
+unused devices: <none>";
+        let stat = source.parse::<MdStat>().unwrap();
+        assert!(stat.personalities().is_empty());
+        assert!(stat.arrays().is_empty());
+    }
+}
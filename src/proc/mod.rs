@@ -4,6 +4,7 @@
 //!
 
 pub mod apm;
+pub mod bootconfig;
 pub mod buddyinfo;
 pub mod cmdline;
 pub mod consoles;
@@ -17,7 +18,10 @@ pub mod filesystems;
 pub mod interrupts;
 pub mod iomem;
 pub mod ioports;
+pub mod kallsyms;
 pub mod kcore;
+pub mod kpagecount;
+pub mod kpageflags;
 pub mod loadavg;
 pub mod locks;
 pub mod mdstat;
@@ -28,7 +32,11 @@ pub mod mounts;
 pub mod mtrr;
 pub mod pagetypeinfo;
 pub mod partitions;
+pub mod pci;
+pub mod schedstat;
+pub mod slabinfo;
 pub mod stat;
 pub mod swaps;
+pub mod sysrq_trigger;
 pub mod uptime;
 pub mod version;
@@ -37,14 +37,17 @@ define_struct! {
     }
 }
 
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 impl FromStr for Module {
     type Err = crate::ProcErr;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let columns: Vec<&str> = s.split_ascii_whitespace().collect();
-        if columns.len() != 6 {
-            return Err("require 6 items to parse module".into());
+        // a 7th column of taint flags such as `(OE)` follows for out-of-tree modules.
+        if columns.len() != 6 && columns.len() != 7 {
+            return Err("require 6 or 7 items to parse module".into());
         }
         let name = columns[0].to_string();
         let mem_size = columns[1].parse::<usize>()?;
@@ -96,6 +99,192 @@ list_impl! {
     modules, "/proc/modules", Module, '\n', 0
 }
 
+/// The dependency graph of loaded modules, returned by [`module_graph()`](fn.module_graph.html).
+///
+/// The used-by column of /proc/modules (`Module::deps`) lists the modules using a module,
+/// the graph also keeps the reverse, the modules each module depends on.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModuleGraph {
+    modules: BTreeMap<String, Module>,
+    used_by: BTreeMap<String, Vec<String>>,
+    depends_on: BTreeMap<String, Vec<String>>,
+}
+
+impl ModuleGraph {
+    pub fn from_modules(modules: Vec<Module>) -> ModuleGraph {
+        let modules: BTreeMap<String, Module> =
+            modules.into_iter().map(|m| (m.name.clone(), m)).collect();
+        let mut used_by = BTreeMap::new();
+        let mut depends_on: BTreeMap<String, Vec<String>> =
+            modules.keys().map(|k| (k.clone(), vec![])).collect();
+        for (name, module) in modules.iter() {
+            // skip `[permanent]` and unknown names.
+            let holders: Vec<String> = module
+                .deps
+                .iter()
+                .filter(|d| modules.contains_key(*d))
+                .cloned()
+                .collect();
+            for holder in holders.iter() {
+                if let Some(deps) = depends_on.get_mut(holder) {
+                    deps.push(name.clone());
+                }
+            }
+            used_by.insert(name.clone(), holders);
+        }
+        ModuleGraph {
+            modules,
+            used_by,
+            depends_on,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Module> {
+        self.modules.get(name)
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = &Module> {
+        self.modules.values()
+    }
+
+    /// Return the modules using `name`, which must be unloaded before it.
+    pub fn used_by(&self, name: &str) -> &[String] {
+        self.used_by.get(name).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// Return the modules `name` depends on.
+    pub fn depends_on(&self, name: &str) -> &[String] {
+        self.depends_on
+            .get(name)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Return the modules to unload, in order, to unload `name`:
+    /// every module using it directly or indirectly, then `name` itself.
+    pub fn unload_order(&self, name: &str) -> Result<Vec<String>, crate::ProcErr> {
+        if !self.modules.contains_key(name) {
+            return Err(format!("module {} is not loaded", name).into());
+        }
+        let mut ret = vec![];
+        self.visit_holders(name, &mut vec![], &mut ret)?;
+        Ok(ret)
+    }
+
+    /// Return all modules in an order safe to unload,
+    /// each module comes after all the modules using it.
+    pub fn unload_order_all(&self) -> Result<Vec<String>, crate::ProcErr> {
+        let mut ret = vec![];
+        for name in self.modules.keys() {
+            if !ret.contains(name) {
+                self.visit_holders(name, &mut vec![], &mut ret)?;
+            }
+        }
+        Ok(ret)
+    }
+
+    fn visit_holders(
+        &self,
+        name: &str,
+        path: &mut Vec<String>,
+        ret: &mut Vec<String>,
+    ) -> Result<(), crate::ProcErr> {
+        if ret.iter().any(|n| n == name) {
+            return Ok(());
+        }
+        if path.iter().any(|n| n == name) {
+            return Err(format!("module dependency loop at {}", name).into());
+        }
+        path.push(name.to_string());
+        for holder in self.used_by(name) {
+            self.visit_holders(holder, path, ret)?;
+        }
+        path.pop();
+        ret.push(name.to_string());
+        Ok(())
+    }
+}
+
+/// Return the [`ModuleGraph`](struct.ModuleGraph.html) of loaded modules.
+pub fn module_graph() -> Result<ModuleGraph, crate::ProcErr> {
+    Ok(ModuleGraph::from_modules(modules()?))
+}
+
+/// Track how long modules stay in Loading or Unloading state across polls of /proc/modules.
+#[derive(Debug, Default)]
+pub struct StateTracker {
+    since: HashMap<String, (State, Instant)>,
+}
+
+impl StateTracker {
+    pub fn new() -> StateTracker {
+        StateTracker::default()
+    }
+
+    /// Record the state of `modules` observed now.
+    pub fn update(&mut self, modules: &[Module]) {
+        self.update_at(modules, Instant::now());
+    }
+
+    fn update_at(&mut self, modules: &[Module], now: Instant) {
+        let mut since = HashMap::new();
+        for module in modules.iter().filter(|m| m.state != State::Live) {
+            let first = match self.since.get(&module.name) {
+                Some((state, first)) if *state == module.state => *first,
+                _ => now,
+            };
+            since.insert(module.name.clone(), (module.state, first));
+        }
+        self.since = since;
+    }
+
+    /// Return the modules staying in Loading or Unloading state for at least `threshold`,
+    /// with the state and how long it has been observed.
+    pub fn stuck(&self, threshold: Duration) -> Vec<(String, State, Duration)> {
+        self.stuck_at(threshold, Instant::now())
+    }
+
+    fn stuck_at(&self, threshold: Duration, now: Instant) -> Vec<(String, State, Duration)> {
+        let mut ret: Vec<(String, State, Duration)> = self
+            .since
+            .iter()
+            .map(|(name, (state, first))| (name.clone(), *state, now.duration_since(*first)))
+            .filter(|(_, _, elapsed)| *elapsed >= threshold)
+            .collect();
+        ret.sort_by(|a, b| a.0.cmp(&b.0));
+        ret
+    }
+}
+
+/// Return the current parameter values of module `name`, read from /sys/module/[name]/parameters.
+///
+/// Parameters which are not readable are skipped,
+/// and an empty map is returned if the module has no parameter.
+pub fn module_parameters(name: &str) -> Result<BTreeMap<String, String>, crate::ProcErr> {
+    let mut ret = BTreeMap::new();
+    let dir = match std::fs::read_dir(format!("/sys/module/{}/parameters", name)) {
+        Ok(dir) => dir,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ret),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in dir {
+        let entry = entry?;
+        let param = entry
+            .file_name()
+            .to_str()
+            .ok_or("contains non-unicode chatacter")?
+            .to_string();
+        match std::fs::read_to_string(entry.path()) {
+            Ok(value) => {
+                ret.insert(param, value.trim_end_matches('\n').to_string());
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::PermissionDenied => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(ret)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -120,6 +309,10 @@ mod test {
             offset: 0,
         };
         assert_eq!(correct, source.parse().unwrap());
+
+        let source = "nvidia 56815616 1 nvidia_modeset, Live 0xffffffffc1200000 (POE)";
+        let module = source.parse::<Module>().unwrap();
+        assert_eq!(0xffffffffc1200000, module.offset);
     }
 
     #[test]
@@ -135,4 +328,66 @@ mod test {
         };
         assert_eq!(correct, source.parse().unwrap());
     }
+
+    fn sample() -> Vec<Module> {
+        let source = "nfs 170109 0 - Live 0x129b0000
+lockd 51593 1 nfs, Live 0x128b0000
+sunrpc 140453 3 nfs,lockd, Live 0x12954000
+fat 38881 1 vfat, Live 0x1287b000
+vfat 12097 0 - Live 0x12823000
+crc32c 16384 0 [permanent], Live 0x12800000";
+        source.lines().map(|l| l.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_module_graph() {
+        let graph = ModuleGraph::from_modules(sample());
+        assert_eq!(
+            &["nfs".to_string(), "lockd".to_string()],
+            graph.used_by("sunrpc")
+        );
+        assert_eq!(
+            &["lockd".to_string(), "sunrpc".to_string()],
+            graph.depends_on("nfs")
+        );
+        assert!(graph.used_by("crc32c").is_empty());
+        assert_eq!(
+            vec!["nfs", "lockd", "sunrpc"],
+            graph.unload_order("sunrpc").unwrap()
+        );
+        assert!(graph.unload_order("xyz").is_err());
+
+        let all = graph.unload_order_all().unwrap();
+        assert_eq!(6, all.len());
+        let pos = |n: &str| all.iter().position(|m| m == n).unwrap();
+        assert!(pos("nfs") < pos("lockd"));
+        assert!(pos("lockd") < pos("sunrpc"));
+        assert!(pos("vfat") < pos("fat"));
+    }
+
+    #[test]
+    fn test_state_tracker() {
+        let mut modules = sample();
+        modules[4].state = State::Loading;
+        let start = Instant::now();
+        let mut tracker = StateTracker::new();
+        tracker.update_at(&modules, start);
+        tracker.update_at(&modules, start + Duration::from_secs(30));
+        let stuck = tracker.stuck_at(Duration::from_secs(10), start + Duration::from_secs(30));
+        assert_eq!(
+            vec![("vfat".to_string(), State::Loading, Duration::from_secs(30))],
+            stuck
+        );
+
+        modules[4].state = State::Unloading;
+        tracker.update_at(&modules, start + Duration::from_secs(40));
+        assert!(tracker
+            .stuck_at(Duration::from_secs(10), start + Duration::from_secs(45))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_module_parameters() {
+        assert!(module_parameters("no_such_module").unwrap().is_empty());
+    }
 }
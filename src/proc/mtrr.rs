@@ -21,3 +21,199 @@
 // interfaces are described below, with sample commands and C code.
 //
 // -- https://www.kernel.org/doc/Documentation/x86/mtrr.txt
+//
+// Since Linux 2.6.29 the count is printed before the memory type:
+//
+//     reg00: base=0x000000000 (    0MB), size= 2048MB, count=1: write-back
+//     reg01: base=0x0f8000000 ( 3968MB), size=    4KB, count=1: write-combining
+//
+// Creating an MTRR with the ASCII interface:
+//
+//     echo "base=0xf8000000 size=0x400000 type=write-combining" >| /proc/mtrr
+//
+// Removing an MTRR with the ASCII interface:
+//
+//     echo "disable=2" >| /proc/mtrr
+
+/// The memory type of a MTRR.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MemoryType {
+    Uncachable,
+    WriteCombining,
+    WriteThrough,
+    WriteProtect,
+    WriteBack,
+    /// A reserved type, printed as `?`. It can't be used by [`add_mtrr()`](fn.add_mtrr.html).
+    Unknown,
+}
+
+impl MemoryType {
+    /// Return the name used by /proc/mtrr.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoryType::Uncachable => "uncachable",
+            MemoryType::WriteCombining => "write-combining",
+            MemoryType::WriteThrough => "write-through",
+            MemoryType::WriteProtect => "write-protect",
+            MemoryType::WriteBack => "write-back",
+            MemoryType::Unknown => "?",
+        }
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for MemoryType {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<MemoryType, crate::ProcErr> {
+        match s {
+            "uncachable" => Ok(MemoryType::Uncachable),
+            "write-combining" => Ok(MemoryType::WriteCombining),
+            "write-through" => Ok(MemoryType::WriteThrough),
+            "write-protect" => Ok(MemoryType::WriteProtect),
+            "write-back" => Ok(MemoryType::WriteBack),
+            "?" => Ok(MemoryType::Unknown),
+            _ => Err(format!("unknow memory type: {}", s).into()),
+        }
+    }
+}
+
+define_struct! {
+    /// Represent a line of /proc/mtrr, returned by [`mtrr()`](fn.mtrr.html).
+    pub struct Mtrr {
+        /// The register index, used by [`disable_mtrr()`](fn.disable_mtrr.html).
+        index: u32,
+        base: u64,
+        /// Size in bytes.
+        size: u64,
+        mem_type: MemoryType,
+        count: u32,
+    }
+}
+
+// ` 256MB` or `    4KB`
+fn parse_size(s: &str) -> Result<u64, crate::ProcErr> {
+    let s = s.trim();
+    let (n, factor) = if let Some(n) = s.strip_suffix("MB") {
+        (n, 1024 * 1024)
+    } else if let Some(n) = s.strip_suffix("KB") {
+        (n, 1024)
+    } else {
+        return Err(format!("unknow mtrr size: {}", s).into());
+    };
+    Ok(n.trim().parse::<u64>()? * factor)
+}
+
+impl FromStr for Mtrr {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Mtrr, crate::ProcErr> {
+        let mut columns = s.splitn(2, ':');
+        let index = columns
+            .next()
+            .ok_or("register not found")?
+            .trim()
+            .strip_prefix("reg")
+            .ok_or("invalid mtrr register")?
+            .parse::<u32>()?;
+        let rest = columns.next().ok_or("no enough fields to parse a Mtrr")?;
+
+        // the order of count and memory type changed in 2.6.29.
+        let mut base = None;
+        let mut size = None;
+        let mut mem_type = None;
+        let mut count = None;
+        for part in rest.split([',', ':']) {
+            let part = part.trim();
+            if let Some(v) = part.strip_prefix("base=") {
+                let v = v.split_ascii_whitespace().next().unwrap_or_default();
+                let v = v.trim_start_matches("0x");
+                base = Some(u64::from_str_radix(v, 16)?);
+            } else if let Some(v) = part.strip_prefix("size=") {
+                size = Some(parse_size(v)?);
+            } else if let Some(v) = part.strip_prefix("count=") {
+                count = Some(v.parse::<u32>()?);
+            } else if !part.is_empty() {
+                mem_type = Some(part.parse::<MemoryType>()?);
+            }
+        }
+
+        Ok(Mtrr {
+            index,
+            base: base.ok_or("base not found")?,
+            size: size.ok_or("size not found")?,
+            mem_type: mem_type.ok_or("memory type not found")?,
+            count: count.ok_or("count not found")?,
+        })
+    }
+}
+
+/// Return parsed content of /proc/mtrr.
+///
+/// The file is empty if the cpu doesn't support MTRR, e.g. in most virtual machines.
+pub fn mtrr() -> Result<Vec<Mtrr>, crate::ProcErr> {
+    let content = std::fs::read_to_string("/proc/mtrr")?;
+    let mut ret = vec![];
+    for line in content.lines() {
+        if !line.trim().is_empty() {
+            ret.push(line.parse::<Mtrr>()?);
+        }
+    }
+    Ok(ret)
+}
+
+test_impl!(mtrr);
+
+/// Create a MTRR through the ASCII interface, require root.
+///
+/// `base` and `size` must be aligned to page size, and `size` must be a power of two.
+pub fn add_mtrr(base: u64, size: u64, mem_type: MemoryType) -> Result<(), crate::ProcErr> {
+    let cmd = format!(
+        "base={:#x} size={:#x} type={}\n",
+        base,
+        size,
+        mem_type.as_str()
+    );
+    std::fs::write("/proc/mtrr", cmd)?;
+    Ok(())
+}
+
+/// Remove the MTRR with register `index` through the ASCII interface, require root.
+pub fn disable_mtrr(index: u32) -> Result<(), crate::ProcErr> {
+    std::fs::write("/proc/mtrr", format!("disable={}\n", index))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_mtrr() {
+        let correct = Mtrr {
+            index: 1,
+            base: 0xe8000000,
+            size: 32 * 1024 * 1024,
+            mem_type: MemoryType::WriteCombining,
+            count: 1,
+        };
+        let source = "reg01: base=0xe8000000 (3712MB), size=  32MB: write-combining, count=1";
+        assert_eq!(correct, source.parse::<Mtrr>().unwrap());
+        let source = "reg01: base=0x0e8000000 ( 3712MB), size=   32MB, count=1: write-combining";
+        assert_eq!(correct, source.parse::<Mtrr>().unwrap());
+
+        let source = "reg02: base=0x0f8000000 ( 3968MB), size=    4KB, count=2: uncachable";
+        let m = source.parse::<Mtrr>().unwrap();
+        assert_eq!(4096, m.size);
+        assert_eq!(MemoryType::Uncachable, m.mem_type);
+        assert_eq!(2, m.count);
+
+        let source = "reg03: base=0x000000000 (    0MB), size=    1MB, count=1: ?";
+        let m = source.parse::<Mtrr>().unwrap();
+        assert_eq!(MemoryType::Unknown, m.mem_type);
+
+        assert!("reg04: base=0x0 (0MB), size= 1MB, count=1: write-nothing"
+            .parse::<Mtrr>()
+            .is_err());
+    }
+}
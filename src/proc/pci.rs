@@ -14,4 +14,328 @@
 // To get a more readable version of this information, type:
 // lspci -vb
 // 
-// -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s1-proc-topfiles#s2-proc-pci
\ No newline at end of file
+// -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s1-proc-topfiles#s2-proc-pci
+//
+// /proc/pci was removed in Linux 2.6.17, the devices are listed in /proc/bus/pci/devices
+// instead, one device per line, separated by tab:
+//
+//     0008	1af41045	0	      4000000004	0	0	0	0	0	0	80000	0	0	0	0	0	0	virtio-pci
+//
+// The columns are bus number and devfn, vendor and device id, IRQ, the base addresses
+// (with the resource flags in the lowest 4 bits) of the 7 resources, the sizes of
+// the 7 resources and the driver name, the last resource is the expansion ROM.
+//
+// /proc/bus/pci/BB/DD.F is the configuration space of the device at bus BB, device DD and
+// function F. Users without CAP_SYS_ADMIN can only read the first 64 bytes, which is
+// the standard header.
+//
+// Reference to [`drivers/pci/proc.c`](https://github.com/torvalds/linux/blob/master/drivers/pci/proc.c)
+// and [`include/uapi/linux/pci_regs.h`](https://github.com/torvalds/linux/blob/master/include/uapi/linux/pci_regs.h)
+
+define_struct! {
+    /// A resource (BAR or expansion ROM) of a PCI device.
+    pub struct PciResource {
+        base: u64,
+        /// The lowest 4 bits of a memory BAR or the lowest 2 bits of an I/O BAR,
+        /// bit 0 is set for I/O space.
+        flags: u8,
+        size: u64,
+    }
+}
+
+define_struct! {
+    /// Represent a line of /proc/bus/pci/devices, returned by [`pci_devices()`](fn.pci_devices.html).
+    pub struct PciDevice {
+        bus: u8,
+        device: u8,
+        function: u8,
+        vendor_id: u16,
+        device_id: u16,
+        irq: u32,
+        /// 6 BARs followed by the expansion ROM, the size is 0 if the resource is not used.
+        resources: Vec<PciResource>,
+        driver: Option<String>,
+    }
+}
+
+impl PciDevice {
+    /// Return the slot name used by lspci, e.g. `00:01.0`.
+    pub fn slot(&self) -> String {
+        format!("{:02x}:{:02x}.{:x}", self.bus, self.device, self.function)
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for PciDevice {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<PciDevice, crate::ProcErr> {
+        let columns: Vec<&str> = s.split('\t').map(|c| c.trim()).collect();
+        if columns.len() < 17 {
+            return Err("require 17 fields to parse a PciDevice".into());
+        }
+        let bus_devfn = u16::from_str_radix(columns[0], 16)?;
+        let devfn = (bus_devfn & 0xff) as u8;
+        let ids = u32::from_str_radix(columns[1], 16)?;
+        let irq = u32::from_str_radix(columns[2], 16)?;
+        let mut resources = Vec::with_capacity(7);
+        for i in 0..7 {
+            let base = u64::from_str_radix(columns[3 + i], 16)?;
+            let size = u64::from_str_radix(columns[10 + i], 16)?;
+            // bits 2-3 of an I/O BAR are address bits.
+            let mask = if base & 0x1 != 0 { 0x3 } else { 0xf };
+            resources.push(PciResource {
+                base: base & !mask,
+                flags: (base & mask) as u8,
+                size,
+            });
+        }
+        let driver = columns
+            .get(17)
+            .filter(|d| !d.is_empty())
+            .map(|d| d.to_string());
+        Ok(PciDevice {
+            bus: (bus_devfn >> 8) as u8,
+            device: devfn >> 3,
+            function: devfn & 0x7,
+            vendor_id: (ids >> 16) as u16,
+            device_id: ids as u16,
+            irq,
+            resources,
+            driver,
+        })
+    }
+}
+
+list_impl! {
+    pci_devices, "/proc/bus/pci/devices", PciDevice, '\n', 0
+}
+
+define_struct! {
+    /// A capability in the capabilities list of the configuration space.
+    pub struct Capability {
+        id: u8,
+        /// The offset of the capability in the configuration space.
+        offset: u8,
+    }
+}
+
+impl Capability {
+    /// Return the name of the capability id, None if it is unknow.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self.id {
+            0x01 => "Power Management",
+            0x02 => "AGP",
+            0x03 => "Vital Product Data",
+            0x04 => "Slot Identification",
+            0x05 => "MSI",
+            0x06 => "CompactPCI Hot Swap",
+            0x07 => "PCI-X",
+            0x08 => "HyperTransport",
+            0x09 => "Vendor-Specific",
+            0x0a => "Debug port",
+            0x0b => "CompactPCI Central Resource Control",
+            0x0c => "PCI Standard Hot-Plug Controller",
+            0x0d => "Bridge subsystem vendor/device ID",
+            0x0e => "AGP 8x",
+            0x0f => "Secure Device",
+            0x10 => "PCI Express",
+            0x11 => "MSI-X",
+            0x12 => "SATA Data/Index Configuration",
+            0x13 => "PCI Advanced Features",
+            0x14 => "Enhanced Allocation",
+            0x15 => "Flattening Portal Bridge",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
+define_struct! {
+    /// The decoded standard header of a configuration space, returned by [`pci_config()`](fn.pci_config.html).
+    pub struct PciConfig {
+        vendor_id: u16,
+        device_id: u16,
+        command: u16,
+        status: u16,
+        revision: u8,
+        /// The base class, e.g. 0x02 for network controller.
+        class: u8,
+        subclass: u8,
+        prog_if: u8,
+        /// 0 for general device, 1 for PCI-to-PCI bridge and 2 for CardBus bridge.
+        header_type: u8,
+        multi_function: bool,
+        /// Not available for PCI-to-PCI bridges.
+        subsystem_vendor_id: Option<u16>,
+        subsystem_id: Option<u16>,
+        interrupt_line: u8,
+        interrupt_pin: u8,
+        /// Only the capabilities inside the readable part of the configuration space.
+        capabilities: Vec<Capability>,
+        raw: Vec<u8>,
+    }
+}
+
+impl PciConfig {
+    /// Return the 24 bit class code, which is class, subclass and programming interface.
+    pub fn class_code(&self) -> u32 {
+        (self.class as u32) << 16 | (self.subclass as u32) << 8 | self.prog_if as u32
+    }
+
+    /// Decode the configuration space read from /proc/bus/pci/BB/DD.F.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PciConfig, crate::ProcErr> {
+        if bytes.len() < 64 {
+            return Err("require 64 bytes to parse a PciConfig".into());
+        }
+        let u16_at = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+
+        let status = u16_at(0x06);
+        let header_type = bytes[0x0e] & 0x7f;
+        let (subsystem_vendor_id, subsystem_id, cap_pointer) = match header_type {
+            0 => (Some(u16_at(0x2c)), Some(u16_at(0x2e)), bytes[0x34]),
+            1 => (None, None, bytes[0x34]),
+            2 if bytes.len() >= 0x44 => (Some(u16_at(0x40)), Some(u16_at(0x42)), bytes[0x14]),
+            _ => (None, None, 0),
+        };
+
+        // the capabilities list exists only if bit 4 of status is set.
+        let mut capabilities = vec![];
+        if status & 0x10 != 0 {
+            let mut offset = cap_pointer & !0x3;
+            // a valid list has at most 48 capabilities, stop at loops.
+            while offset >= 0x40 && (offset as usize) + 1 < bytes.len() && capabilities.len() < 48 {
+                let id = bytes[offset as usize];
+                if id == 0xff {
+                    break;
+                }
+                capabilities.push(Capability { id, offset });
+                offset = bytes[offset as usize + 1] & !0x3;
+            }
+        }
+
+        Ok(PciConfig {
+            vendor_id: u16_at(0x00),
+            device_id: u16_at(0x02),
+            command: u16_at(0x04),
+            status,
+            revision: bytes[0x08],
+            prog_if: bytes[0x09],
+            subclass: bytes[0x0a],
+            class: bytes[0x0b],
+            header_type,
+            multi_function: bytes[0x0e] & 0x80 != 0,
+            subsystem_vendor_id,
+            subsystem_id,
+            interrupt_line: bytes[0x3c],
+            interrupt_pin: bytes[0x3d],
+            capabilities,
+            raw: bytes.to_vec(),
+        })
+    }
+}
+
+/// Read and decode the configuration space of the device at `bus`, `device` and `function` in domain 0.
+///
+/// Devices of other domains are in /proc/bus/pci/DDDD:BB, which is not supported since
+/// /proc/bus/pci/devices doesn't tell the domain, a device of another domain with the same
+/// bus number may be read or NotFound may be returned on hosts with multiple PCI domains.
+pub fn pci_config(bus: u8, device: u8, function: u8) -> Result<PciConfig, crate::ProcErr> {
+    let path = format!("/proc/bus/pci/{:02x}/{:02x}.{:x}", bus, device, function);
+    let bytes = std::fs::read(path)?;
+    PciConfig::from_bytes(&bytes)
+}
+
+/// Read and decode the configuration space of `dev`, see [`pci_config()`](fn.pci_config.html)
+/// for the limitation of PCI domains.
+pub fn pci_config_of(dev: &PciDevice) -> Result<PciConfig, crate::ProcErr> {
+    pci_config(dev.bus, dev.device, dev.function)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_pci_device() {
+        let source = "0008\t1af41045\t0\t      4000000004\t               0\t               0\t               0\t               0\t               0\t               0\t           80000\t               0\t               0\t               0\t               0\t               0\t               0\tvirtio-pci";
+        let dev = source.parse::<PciDevice>().unwrap();
+        assert_eq!("00:01.0", dev.slot());
+        assert_eq!(0x1af4, dev.vendor_id);
+        assert_eq!(0x1045, dev.device_id);
+        assert_eq!(0x4000000000, dev.resources[0].base);
+        assert_eq!(4, dev.resources[0].flags);
+        assert_eq!(0x80000, dev.resources[0].size);
+        assert_eq!(7, dev.resources.len());
+        assert_eq!(Some("virtio-pci".to_string()), dev.driver);
+
+        let source = "0020\t80867010\t0\t0\t0\t0\t0\td805\t0\t0\t0\t0\t0\t0\t10\t0\t0\tata_piix";
+        let dev = source.parse::<PciDevice>().unwrap();
+        assert_eq!(0xd804, dev.resources[4].base);
+        assert_eq!(1, dev.resources[4].flags);
+        assert_eq!(0x10, dev.resources[4].size);
+
+        let source = "00f9\t80860d57\tb\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t0\t";
+        let dev = source.parse::<PciDevice>().unwrap();
+        assert_eq!("00:1f.1", dev.slot());
+        assert_eq!(11, dev.irq);
+        assert_eq!(None, dev.driver);
+    }
+
+    #[test]
+    fn test_parse_pci_config() {
+        let mut bytes = vec![0u8; 256];
+        bytes[..16].copy_from_slice(&[
+            0xf4, 0x1a, 0x45, 0x10, 0x06, 0x04, 0x10, 0x00, 0x01, 0x00, 0x80, 0x02, 0x00, 0x00,
+            0x80, 0x00,
+        ]);
+        bytes[0x2c..0x30].copy_from_slice(&[0xf4, 0x1a, 0x45, 0x10]);
+        bytes[0x34] = 0x40;
+        bytes[0x3d] = 1;
+        bytes[0x40..0x42].copy_from_slice(&[0x09, 0x50]);
+        bytes[0x50..0x52].copy_from_slice(&[0x11, 0x60]);
+        bytes[0x60..0x62].copy_from_slice(&[0x10, 0x00]);
+
+        let config = PciConfig::from_bytes(&bytes).unwrap();
+        assert_eq!(0x1af4, config.vendor_id);
+        assert_eq!(0x1045, config.device_id);
+        assert_eq!(0x028000, config.class_code());
+        assert_eq!(1, config.revision);
+        assert_eq!(0, config.header_type);
+        assert!(config.multi_function);
+        assert_eq!(Some(0x1af4), config.subsystem_vendor_id);
+        assert_eq!(Some(0x1045), config.subsystem_id);
+        assert_eq!(1, config.interrupt_pin);
+        let ids: Vec<u8> = config.capabilities.iter().map(|c| c.id).collect();
+        assert_eq!(vec![0x09, 0x11, 0x10], ids);
+        assert_eq!(Some("MSI-X"), config.capabilities[1].name());
+
+        // only the header is readable without CAP_SYS_ADMIN.
+        let config = PciConfig::from_bytes(&bytes[..64]).unwrap();
+        assert!(config.capabilities.is_empty());
+        assert!(PciConfig::from_bytes(&bytes[..32]).is_err());
+    }
+
+    #[test]
+    fn test_pci_config() {
+        let devices = match pci_devices() {
+            Ok(devices) => devices,
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => panic!("{:?}", e),
+        };
+        for dev in devices.iter() {
+            // the device may be in another PCI domain.
+            let config = match pci_config_of(dev) {
+                Ok(config) => config,
+                Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                    continue
+                }
+                Err(e) => panic!("{:?}", e),
+            };
+            if dev.vendor_id != config.vendor_id {
+                continue;
+            }
+            assert_eq!(dev.device_id, config.device_id);
+        }
+    }
+}
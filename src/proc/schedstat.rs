@@ -0,0 +1,341 @@
+// /proc/schedstat
+//
+// Version 15 of schedstats dropped counters for some sched_yield:
+// yld_exp_empty, yld_act_empty and yld_both_empty. Otherwise, it is
+// identical to version 14.
+//
+// Version 16 changed the order of definitions in cpu_idle_type enum,
+// which changed the order of [CPU_MAX_IDLE_TYPES] columns in
+// show_schedstat(). In particular the position of CPU_IDLE
+// and __CPU_NOT_IDLE changed places. The size of the array is unchanged.
+//
+// Version 17 of schedstats removed 'lb_imbalance' field as it has no
+// significance anymore and instead added more relevant fields namely
+// 'lb_imbalance_load', 'lb_imbalance_util', 'lb_imbalance_task' and
+// 'lb_imbalance_misfit'. The domain field prints the name of the
+// corresponding sched domain from this version onwards.
+//
+// The format for /proc/schedstat looks like:
+//
+//     version 15
+//     timestamp 4297299139
+//     cpu0 0 0 0 0 0 0 1145 0 0
+//     domain0 00000003 <36 fields>
+//
+// cpu<N> 1 2 3 4 5 6 7 8 9
+//
+//     First field is a sched_yield() statistic:
+//
+//     1) # of times sched_yield() was called
+//
+//     Next three are schedule() statistics:
+//
+//     2) This field is a legacy array expiration count field used in the O(1)
+//        scheduler. We kept it for ABI compatibility, but it is always set to zero.
+//     3) # of times schedule() was called
+//     4) # of times schedule() left the processor idle
+//
+//     Next two are try_to_wake_up() statistics:
+//
+//     5) # of times try_to_wake_up() was called
+//     6) # of times try_to_wake_up() was called to wake up the local cpu
+//
+//     Next three are statistics describing scheduling latency:
+//
+//     7) sum of all time spent running by tasks on this processor (in nanoseconds)
+//     8) sum of all time spent waiting to run by tasks on this processor (in
+//        nanoseconds)
+//     9) # of timeslices run on this cpu
+//
+// domain<N> <cpumask> 1 2 3 ... 36
+//
+//     The first field is a bit mask indicating what cpus this domain operates
+//     over.
+//
+//     The next 24 are a variety of sched_balance_rq() statistics in grouped
+//     into types of idleness (idle, busy, and newly idle), for each type:
+//     count, balanced, failed, imbalance, gained, hot_gained, nobusyq, nobusyg.
+//
+//     Next three are active_load_balance() statistics:
+//     count, failed, pushed.
+//
+//     Next three are sched_balance_exec() statistics:
+//     count, balanced, pushed.
+//
+//     Next three are sched_balance_fork() statistics:
+//     count, balanced, pushed.
+//
+//     Next three are try_to_wake_up() statistics:
+//     wake_remote, move_affine, move_balance.
+//
+// -- https://www.kernel.org/doc/Documentation/scheduler/sched-stats.rst
+
+define_struct! {
+    /// Represent the content of /proc/schedstat, returned by [`schedstat()`](fn.schedstat.html).
+    ///
+    /// Only version 15 and later are supported.
+    pub struct SchedStat {
+        version: u32,
+        timestamp: u64,
+        cpus: Vec<CpuSchedStat>,
+    }
+}
+
+define_struct! {
+    /// Represent a `cpu<N>` line and the `domain<N>` lines following it.
+    pub struct CpuSchedStat {
+        cpu: usize,
+        yld_count: u64,
+        sched_count: u64,
+        sched_goidle: u64,
+        ttwu_count: u64,
+        ttwu_local: u64,
+        /// Time spent running by tasks on this cpu, in nanoseconds.
+        rq_cpu_time: u64,
+        /// Time spent waiting to run by tasks on this cpu, in nanoseconds.
+        run_delay: u64,
+        pcount: u64,
+        domains: Vec<DomainSchedStat>,
+    }
+}
+
+define_struct! {
+    /// Represent a `domain<N>` line.
+    pub struct DomainSchedStat {
+        domain: usize,
+        /// The sched domain name, only available since version 17.
+        name: Option<String>,
+        cpumask: String,
+        lb_idle: LoadBalance,
+        lb_busy: LoadBalance,
+        lb_newly_idle: LoadBalance,
+        alb_count: u64,
+        alb_failed: u64,
+        alb_pushed: u64,
+        sbe_count: u64,
+        sbe_balanced: u64,
+        sbe_pushed: u64,
+        sbf_count: u64,
+        sbf_balanced: u64,
+        sbf_pushed: u64,
+        ttwu_wake_remote: u64,
+        ttwu_move_affine: u64,
+        ttwu_move_balance: u64,
+    }
+}
+
+define_struct! {
+    /// Load balancing counters of one idle type.
+    pub struct LoadBalance {
+        count: u64,
+        balanced: u64,
+        failed: u64,
+        /// Contains one element before version 17,
+        /// and load, util, task, misfit imbalance since version 17.
+        imbalance: Vec<u64>,
+        gained: u64,
+        hot_gained: u64,
+        nobusyq: u64,
+        nobusyg: u64,
+    }
+}
+
+impl LoadBalance {
+    fn from_fields(fields: &[u64]) -> LoadBalance {
+        let n = fields.len();
+        LoadBalance {
+            count: fields[0],
+            balanced: fields[1],
+            failed: fields[2],
+            imbalance: fields[3..n - 4].to_vec(),
+            gained: fields[n - 4],
+            hot_gained: fields[n - 3],
+            nobusyq: fields[n - 2],
+            nobusyg: fields[n - 1],
+        }
+    }
+}
+
+impl CpuSchedStat {
+    fn from_line(line: &str) -> Result<CpuSchedStat, crate::ProcErr> {
+        let columns: Vec<&str> = line.split_ascii_whitespace().collect();
+        if columns.len() != 10 {
+            return Err("require 10 fields to parse a cpu schedstat".into());
+        }
+        let cpu = columns[0].trim_start_matches("cpu").parse::<usize>()?;
+        let mut fields = [0u64; 9];
+        for (f, v) in fields.iter_mut().zip(columns[1..].iter()) {
+            *f = v.parse::<u64>()?;
+        }
+        Ok(CpuSchedStat {
+            cpu,
+            yld_count: fields[0],
+            sched_count: fields[2],
+            sched_goidle: fields[3],
+            ttwu_count: fields[4],
+            ttwu_local: fields[5],
+            rq_cpu_time: fields[6],
+            run_delay: fields[7],
+            pcount: fields[8],
+            domains: vec![],
+        })
+    }
+}
+
+impl DomainSchedStat {
+    fn from_line(line: &str, version: u32) -> Result<DomainSchedStat, crate::ProcErr> {
+        let mut columns = line.split_ascii_whitespace();
+        let domain = columns
+            .next()
+            .ok_or("domain not found")?
+            .trim_start_matches("domain")
+            .parse::<usize>()?;
+        let name = if version >= 17 {
+            Some(columns.next().ok_or("domain name not found")?.to_string())
+        } else {
+            None
+        };
+        let cpumask = columns.next().ok_or("cpumask not found")?.to_string();
+        let mut fields = vec![];
+        for v in columns {
+            fields.push(v.parse::<u64>()?);
+        }
+
+        let lb_len = if version >= 17 { 11 } else { 8 };
+        if fields.len() != lb_len * 3 + 12 {
+            return Err(format!("unexpected domain field number: {}", fields.len()).into());
+        }
+        let lb0 = LoadBalance::from_fields(&fields[0..lb_len]);
+        let lb1 = LoadBalance::from_fields(&fields[lb_len..lb_len * 2]);
+        let lb_newly_idle = LoadBalance::from_fields(&fields[lb_len * 2..lb_len * 3]);
+        // version 16 swapped the position of CPU_IDLE and CPU_NOT_IDLE.
+        let (lb_idle, lb_busy) = if version >= 16 {
+            (lb1, lb0)
+        } else {
+            (lb0, lb1)
+        };
+        let rest = &fields[lb_len * 3..];
+
+        Ok(DomainSchedStat {
+            domain,
+            name,
+            cpumask,
+            lb_idle,
+            lb_busy,
+            lb_newly_idle,
+            alb_count: rest[0],
+            alb_failed: rest[1],
+            alb_pushed: rest[2],
+            sbe_count: rest[3],
+            sbe_balanced: rest[4],
+            sbe_pushed: rest[5],
+            sbf_count: rest[6],
+            sbf_balanced: rest[7],
+            sbf_pushed: rest[8],
+            ttwu_wake_remote: rest[9],
+            ttwu_move_affine: rest[10],
+            ttwu_move_balance: rest[11],
+        })
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for SchedStat {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<SchedStat, crate::ProcErr> {
+        let mut lines = s.lines();
+        let version = lines
+            .next()
+            .ok_or("version not found")?
+            .trim_start_matches("version")
+            .trim()
+            .parse::<u32>()?;
+        if version < 15 {
+            return Err(format!("unsupported schedstat version: {}", version).into());
+        }
+        let timestamp = lines
+            .next()
+            .ok_or("timestamp not found")?
+            .trim_start_matches("timestamp")
+            .trim()
+            .parse::<u64>()?;
+
+        let mut cpus: Vec<CpuSchedStat> = vec![];
+        for line in lines {
+            if line.starts_with("cpu") {
+                cpus.push(CpuSchedStat::from_line(line)?);
+            } else if line.starts_with("domain") {
+                let domain = DomainSchedStat::from_line(line, version)?;
+                cpus.last_mut()
+                    .ok_or("domain appears before cpu")?
+                    .domains
+                    .push(domain);
+            } else if !line.trim().is_empty() {
+                return Err(format!("unknow line in schedstat: {}", line).into());
+            }
+        }
+
+        Ok(SchedStat {
+            version,
+            timestamp,
+            cpus,
+        })
+    }
+}
+
+instance_impl! {
+    schedstat, "/proc/schedstat", SchedStat
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_schedstat_v15() {
+        let source = "version 15
+timestamp 4295040113
+cpu0 0 0 3442 1257 1865 1121 1357207810 123456 2178
+domain0 00000003 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33 34 35 36
+cpu1 1 0 2 3 4 5 6 7 8";
+        let ss = source.parse::<SchedStat>().unwrap();
+        assert_eq!(15, ss.version);
+        assert_eq!(4295040113, ss.timestamp);
+        assert_eq!(2, ss.cpus.len());
+        assert_eq!(123456, ss.cpus[0].run_delay);
+        assert!(ss.cpus[1].domains.is_empty());
+
+        let domain = &ss.cpus[0].domains[0];
+        assert_eq!(None, domain.name);
+        assert_eq!("00000003", domain.cpumask);
+        assert_eq!(1, domain.lb_idle.count);
+        assert_eq!(vec![4], domain.lb_idle.imbalance);
+        assert_eq!(9, domain.lb_busy.count);
+        assert_eq!(17, domain.lb_newly_idle.count);
+        assert_eq!(25, domain.alb_count);
+        assert_eq!(36, domain.ttwu_move_balance);
+    }
+
+    #[test]
+    fn test_parse_schedstat_v17() {
+        let source = "version 17
+timestamp 4295040113
+cpu0 0 0 1 2 3 4 5 6 7
+domain0 SMT 00000003 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33 34 35 36 37 38 39 40 41 42 43 44 45";
+        let ss = source.parse::<SchedStat>().unwrap();
+        let domain = &ss.cpus[0].domains[0];
+        assert_eq!(Some("SMT".to_string()), domain.name);
+        assert_eq!(12, domain.lb_idle.count);
+        assert_eq!(vec![15, 16, 17, 18], domain.lb_idle.imbalance);
+        assert_eq!(1, domain.lb_busy.count);
+        assert_eq!(11, domain.lb_busy.nobusyg);
+        assert_eq!(34, domain.alb_count);
+        assert_eq!(45, domain.ttwu_move_balance);
+    }
+
+    #[test]
+    fn test_parse_schedstat_unsupported() {
+        assert!("version 14\ntimestamp 1".parse::<SchedStat>().is_err());
+    }
+}
@@ -41,4 +41,196 @@
 // OBJ/SLAB — The number of objects that fit into a slab.
 // CACHE SIZE — The cache size of the slab.
 // NAME — The name of the slab.
-// For more information on the /usr/bin/slabtop program, refer to the slabtop man page.
\ No newline at end of file
+// For more information on the /usr/bin/slabtop program, refer to the slabtop man page.
+//
+// -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s2-proc-slabinfo
+//
+// Since Linux 2.6.10 the file is in slabinfo 2.x format, and is only readable by root:
+//
+//     slabinfo - version: 2.1
+//     # name            <active_objs> <num_objs> <objsize> <objperslab> <pagesperslab> : tunables <limit> <batchcount> <sharedfactor> : slabdata <active_slabs> <num_slabs> <sharedavail>
+//     ext4_groupinfo_4k   2054   2054    152   26    1 : tunables    0    0    0 : slabdata     79     79      0
+//
+// The tunables are always 0 with SLUB.
+
+define_struct! {
+    /// Represent a cache line in /proc/slabinfo.
+    pub struct Slab {
+        name: String,
+        active_objs: u64,
+        num_objs: u64,
+        /// The size of an object, in bytes.
+        objsize: u64,
+        objperslab: u64,
+        pagesperslab: u64,
+        limit: u64,
+        batchcount: u64,
+        sharedfactor: u64,
+        active_slabs: u64,
+        num_slabs: u64,
+        sharedavail: u64,
+    }
+}
+
+impl Slab {
+    /// Return the memory used by the cache in bytes, which is num_slabs * pagesperslab * `page_size`.
+    pub fn memory(&self, page_size: u64) -> u64 {
+        self.num_slabs * self.pagesperslab * page_size
+    }
+}
+
+define_struct! {
+    /// Represent the content of /proc/slabinfo, returned by [`slabinfo()`](fn.slabinfo.html).
+    pub struct SlabInfo {
+        version: String,
+        slabs: Vec<Slab>,
+    }
+}
+
+impl SlabInfo {
+    /// Return caches sorted by memory in descending order, like slabtop(1) sorting by cache size.
+    pub fn sorted_by_memory(&self, page_size: u64) -> Vec<&Slab> {
+        let mut ret: Vec<&Slab> = self.slabs.iter().collect();
+        ret.sort_by_key(|s| std::cmp::Reverse(s.memory(page_size)));
+        ret
+    }
+}
+
+use std::str::FromStr;
+impl FromStr for Slab {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<Slab, crate::ProcErr> {
+        let columns: Vec<&str> = s.split_ascii_whitespace().collect();
+        if columns.len() != 16 || columns[6] != ":" || columns[7] != "tunables" {
+            return Err(format!("unknow slabinfo line: {}", s).into());
+        }
+        if columns[11] != ":" || columns[12] != "slabdata" {
+            return Err(format!("unknow slabinfo line: {}", s).into());
+        }
+        let mut fields = [0u64; 11];
+        let indexes = [1, 2, 3, 4, 5, 8, 9, 10, 13, 14, 15];
+        for (f, i) in fields.iter_mut().zip(indexes.iter()) {
+            *f = columns[*i].parse::<u64>()?;
+        }
+        Ok(Slab {
+            name: columns[0].to_string(),
+            active_objs: fields[0],
+            num_objs: fields[1],
+            objsize: fields[2],
+            objperslab: fields[3],
+            pagesperslab: fields[4],
+            limit: fields[5],
+            batchcount: fields[6],
+            sharedfactor: fields[7],
+            active_slabs: fields[8],
+            num_slabs: fields[9],
+            sharedavail: fields[10],
+        })
+    }
+}
+
+impl FromStr for SlabInfo {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<SlabInfo, crate::ProcErr> {
+        let mut lines = s.lines();
+        let version = lines
+            .next()
+            .ok_or("version not found")?
+            .strip_prefix("slabinfo - version:")
+            .ok_or("invalid slabinfo header")?
+            .trim()
+            .to_string();
+        if !version.starts_with("2.") {
+            return Err(format!("unsupported slabinfo version: {}", version).into());
+        }
+
+        let mut slabs = vec![];
+        for line in lines {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            slabs.push(line.parse::<Slab>()?);
+        }
+        Ok(SlabInfo { version, slabs })
+    }
+}
+
+/// Return parsed content of /proc/slabinfo.
+///
+/// The file is only readable by root, use
+/// [`ProcErr::is_permission_denied()`](../../enum.ProcErr.html#method.is_permission_denied)
+/// to check whether it fails for this reason.
+pub fn slabinfo() -> Result<SlabInfo, crate::ProcErr> {
+    let content = std::fs::read_to_string("/proc/slabinfo")?;
+    content.trim().parse()
+}
+
+/// Return the `n` caches using most memory with the memory in bytes, like slabtop(1).
+pub fn top_caches(n: usize) -> Result<Vec<(Slab, u64)>, crate::ProcErr> {
+    let page_size = crate::pid::auxv::page_size()? as u64;
+    let info = slabinfo()?;
+    Ok(info
+        .sorted_by_memory(page_size)
+        .into_iter()
+        .take(n)
+        .map(|s| (s.clone(), s.memory(page_size)))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_slabinfo() {
+        let source = "slabinfo - version: 2.1
+# name            <active_objs> <num_objs> <objsize> <objperslab> <pagesperslab> : tunables <limit> <batchcount> <sharedfactor> : slabdata <active_slabs> <num_slabs> <sharedavail>
+ext4_groupinfo_4k   2054   2054    152   26    1 : tunables    0    0    0 : slabdata     79     79      0
+kmalloc-8k           272    280   8192    4    8 : tunables    0    0    0 : slabdata     70     70      0
+dentry             90456  91812    192   21    1 : tunables  120   60    8 : slabdata   4372   4372     12";
+        let info = source.parse::<SlabInfo>().unwrap();
+        assert_eq!("2.1", info.version());
+        assert_eq!(3, info.slabs().len());
+        let dentry = Slab {
+            name: "dentry".to_string(),
+            active_objs: 90456,
+            num_objs: 91812,
+            objsize: 192,
+            objperslab: 21,
+            pagesperslab: 1,
+            limit: 120,
+            batchcount: 60,
+            sharedfactor: 8,
+            active_slabs: 4372,
+            num_slabs: 4372,
+            sharedavail: 12,
+        };
+        assert_eq!(dentry, info.slabs()[2]);
+
+        let sorted: Vec<&str> = info
+            .sorted_by_memory(4096)
+            .iter()
+            .map(|s| s.name().as_str())
+            .collect();
+        assert_eq!(vec!["dentry", "kmalloc-8k", "ext4_groupinfo_4k"], sorted);
+        assert_eq!(70 * 8 * 4096, info.slabs()[1].memory(4096));
+    }
+
+    #[test]
+    fn test_parse_slabinfo_version() {
+        assert!("slabinfo - version: 1.1\n".parse::<SlabInfo>().is_err());
+        assert!("version: 2.1\n".parse::<SlabInfo>().is_err());
+    }
+
+    #[test]
+    fn test_impl() {
+        match top_caches(5) {
+            Ok(top) => assert!(top.len() <= 5),
+            Err(e) if e.is_permission_denied() => {}
+            Err(crate::ProcErr::IO(ref e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+}
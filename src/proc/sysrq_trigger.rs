@@ -2,4 +2,279 @@
 // Using the echo command to write to this file, a remote root user can execute most System Request Key commands remotely as if at the local terminal. To echo values to this file, the /proc/sys/kernel/sysrq must be set to a value other than 0. For more information about the System Request Key, refer to Section 5.3.9.3, “ /proc/sys/kernel/ ”.
 // Although it is possible to write to this file, it cannot be read, even by the root user.
 // 
-// -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s1-proc-topfiles#s2-proc-sysrq-trigger
\ No newline at end of file
+// -- https://access.redhat.com/documentation/en-us/red_hat_enterprise_linux/5/html/deployment_guide/s1-proc-topfiles#s2-proc-sysrq-trigger
+//
+// /proc/sys/kernel/sysrq controls the functions allowed to be invoked via the SysRq key:
+//
+//     0 - disable sysrq completely
+//     1 - enable all functions of sysrq
+//     >1 - bitmask of allowed sysrq functions (see below for detailed function
+//          description):
+//          2 =   0x2 - enable control of console logging level
+//          4 =   0x4 - enable control of keyboard (SAK, unraw)
+//          8 =   0x8 - enable debugging dumps of processes etc.
+//         16 =  0x10 - enable sync command
+//         32 =  0x20 - enable remount read-only
+//         64 =  0x40 - enable signalling of processes (term, kill, oom-kill)
+//        128 =  0x80 - allow reboot/poweroff
+//        256 = 0x100 - allow nicing of all RT tasks
+//
+// Note that the value of /proc/sys/kernel/sysrq influences only the invocation
+// via a keyboard. Invocation of any operation via /proc/sysrq-trigger is
+// always allowed (by a user with admin privileges).
+//
+// -- https://www.kernel.org/doc/Documentation/admin-guide/sysrq.rst
+
+/// SysRq commands which don't kill processes, lose data or stop the system.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SysRq {
+    /// Set the console log level, from 0 to 9.
+    LogLevel(u8),
+    /// Switch off keyboard raw mode.
+    UnRaw,
+    /// Sync all mounted filesystems.
+    Sync,
+    /// Thaw filesystems frozen by the FIFREEZE ioctl.
+    Thaw,
+    /// Make RT tasks nice-able.
+    NiceAllRt,
+    /// Show all locks that are held.
+    ShowLocks,
+    /// Show a stack backtrace for all active CPUs.
+    ShowAllCpus,
+    /// Show current memory info.
+    ShowMemory,
+    /// Dump the current registers and flags.
+    ShowRegisters,
+    /// Dump per CPU lists of all armed hrtimers and clockevent devices.
+    ShowTimers,
+    /// Dump a list of current tasks and their information.
+    ShowTasks,
+    /// Dump tasks that are in uninterruptible (blocked) state.
+    ShowBlocked,
+    /// Dump the ftrace buffer.
+    ShowFtrace,
+}
+
+impl SysRq {
+    /// Return the command character written to /proc/sysrq-trigger.
+    pub fn key(&self) -> char {
+        match self {
+            SysRq::LogLevel(level) => (b'0' + level.min(&9)) as char,
+            SysRq::UnRaw => 'r',
+            SysRq::Sync => 's',
+            SysRq::Thaw => 'j',
+            SysRq::NiceAllRt => 'n',
+            SysRq::ShowLocks => 'd',
+            SysRq::ShowAllCpus => 'l',
+            SysRq::ShowMemory => 'm',
+            SysRq::ShowRegisters => 'p',
+            SysRq::ShowTimers => 'q',
+            SysRq::ShowTasks => 't',
+            SysRq::ShowBlocked => 'w',
+            SysRq::ShowFtrace => 'z',
+        }
+    }
+}
+
+/// SysRq commands which may kill processes, lose data or stop the system,
+/// sent by [`trigger_destructive()`](fn.trigger_destructive.html).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DestructiveSysRq {
+    /// Immediately reboot the system without syncing or unmounting disks.
+    Reboot,
+    /// Perform a system crash by a NULL pointer dereference.
+    Crash,
+    /// Call the OOM killer to kill a memory hog process.
+    OomKill,
+    /// Send a SIGTERM to all processes, except for init.
+    TermAll,
+    /// Send a SIGKILL to all processes, except for init.
+    KillAll,
+    /// Kill all programs on the current virtual console (Secure Access Key).
+    SecureAccessKey,
+    /// Shut the system off.
+    PowerOff,
+    /// Remount all mounted filesystems read-only.
+    RemountReadOnly,
+}
+
+impl DestructiveSysRq {
+    /// Return the command character written to /proc/sysrq-trigger.
+    pub fn key(&self) -> char {
+        match self {
+            DestructiveSysRq::Reboot => 'b',
+            DestructiveSysRq::Crash => 'c',
+            DestructiveSysRq::OomKill => 'f',
+            DestructiveSysRq::TermAll => 'e',
+            DestructiveSysRq::KillAll => 'i',
+            DestructiveSysRq::SecureAccessKey => 'k',
+            DestructiveSysRq::PowerOff => 'o',
+            DestructiveSysRq::RemountReadOnly => 'u',
+        }
+    }
+}
+
+/// The explicit opt-in required by [`trigger_destructive()`](fn.trigger_destructive.html).
+#[derive(Debug)]
+pub struct AllowDestructive {
+    _private: (),
+}
+
+impl AllowDestructive {
+    /// Confirm that the caller intends to send a destructive SysRq command.
+    pub fn i_know_what_i_am_doing() -> AllowDestructive {
+        AllowDestructive { _private: () }
+    }
+}
+
+fn write_trigger(key: char) -> Result<(), crate::ProcErr> {
+    std::fs::write("/proc/sysrq-trigger", key.to_string())?;
+    Ok(())
+}
+
+/// Send `cmd` to /proc/sysrq-trigger, require root.
+pub fn trigger(cmd: SysRq) -> Result<(), crate::ProcErr> {
+    write_trigger(cmd.key())
+}
+
+/// Send a destructive `cmd` to /proc/sysrq-trigger, require root.
+///
+/// The `AllowDestructive` argument is consumed so that every call has to opt in again.
+pub fn trigger_destructive(
+    cmd: DestructiveSysRq,
+    _allow: AllowDestructive,
+) -> Result<(), crate::ProcErr> {
+    write_trigger(cmd.key())
+}
+
+/// All the bits of the functions in /proc/sys/kernel/sysrq.
+const ALL_FUNCTIONS: u32 = 0x1fe;
+
+define_struct! {
+    /// Represent the content of /proc/sys/kernel/sysrq, returned by [`sysrq_mask()`](fn.sysrq_mask.html).
+    pub struct SysRqMask(u32);
+}
+
+impl From<u32> for SysRqMask {
+    fn from(mask: u32) -> SysRqMask {
+        SysRqMask(mask)
+    }
+}
+
+impl SysRqMask {
+    /// Return true if sysrq is disabled completely (0).
+    pub fn is_disabled(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return true if all functions are enabled (1).
+    pub fn is_all_enabled(&self) -> bool {
+        self.0 == 1
+    }
+}
+
+macro_rules! flag_methods {
+    ($(
+        $method: ident, $set_method: ident, $bit: expr
+    );*) => {
+        impl SysRqMask {$(
+            #[doc="Return true if the functions of bit "]
+            #[doc=stringify!($bit)]
+            #[doc=" are allowed, which is also true if all functions are enabled."]
+            pub fn $method(&self) -> bool {
+                self.0 == 1 || self.0 & (1 << $bit) != 0
+            }
+
+            #[doc="Set or clear bit "]
+            #[doc=stringify!($bit)]
+            #[doc=". A mask enabling all functions is expanded to every bit first."]
+            pub fn $set_method(&mut self, value: bool) -> &mut SysRqMask {
+                if self.0 == 1 {
+                    self.0 = ALL_FUNCTIONS;
+                }
+                if value {
+                    self.0 |= 1 << $bit;
+                } else {
+                    self.0 &= !(1 << $bit);
+                }
+                self
+            }
+        )*}
+    };
+}
+
+flag_methods! {
+    log_level, set_log_level, 1;
+    keyboard, set_keyboard, 2;
+    dumps, set_dumps, 3;
+    sync, set_sync, 4;
+    remount_read_only, set_remount_read_only, 5;
+    signal, set_signal, 6;
+    reboot, set_reboot, 7;
+    nice_rt, set_nice_rt, 8
+}
+
+use std::str::FromStr;
+impl FromStr for SysRqMask {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<SysRqMask, crate::ProcErr> {
+        Ok(SysRqMask(s.trim().parse::<u32>()?))
+    }
+}
+
+instance_impl! {
+    sysrq_mask, "/proc/sys/kernel/sysrq", SysRqMask
+}
+
+/// Write /proc/sys/kernel/sysrq, require root.
+pub fn set_sysrq_mask(mask: SysRqMask) -> Result<(), crate::ProcErr> {
+    std::fs::write("/proc/sys/kernel/sysrq", mask.0.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keys() {
+        assert_eq!('s', SysRq::Sync.key());
+        assert_eq!('w', SysRq::ShowBlocked.key());
+        assert_eq!('3', SysRq::LogLevel(3).key());
+        assert_eq!('9', SysRq::LogLevel(42).key());
+        assert_eq!('c', DestructiveSysRq::Crash.key());
+        assert_eq!('b', DestructiveSysRq::Reboot.key());
+        assert_eq!('u', DestructiveSysRq::RemountReadOnly.key());
+    }
+
+    #[test]
+    fn test_parse_sysrq_mask() {
+        let mask = "176".parse::<SysRqMask>().unwrap();
+        assert!(mask.sync());
+        assert!(mask.remount_read_only());
+        assert!(mask.reboot());
+        assert!(!mask.signal());
+        assert!(!mask.is_disabled());
+
+        let mut mask = SysRqMask::from(0);
+        assert!(mask.is_disabled());
+        mask.set_sync(true).set_dumps(true);
+        assert_eq!(SysRqMask(0x18), mask);
+        mask.set_sync(false);
+        assert_eq!(SysRqMask(0x8), mask);
+
+        let mut mask = SysRqMask::from(1);
+        assert!(mask.is_all_enabled());
+        assert!(mask.signal());
+        mask.set_reboot(false);
+        assert_eq!(SysRqMask(0x17e), mask);
+        assert!(!mask.reboot());
+        assert!(mask.signal());
+
+        let mut mask = SysRqMask::from(1);
+        mask.set_sync(true);
+        assert_eq!(SysRqMask(0x1fe), mask);
+    }
+}
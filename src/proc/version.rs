@@ -77,6 +77,196 @@ instance_impl! {
     version, "/proc/version", Version
 }
 
+/// The distribution detected from the suffix of a kernel release.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Distro {
+    /// `el7`, `el8_9`, the major version of RHEL and its rebuilds.
+    RedHat(u32),
+    /// `fc39`.
+    Fedora(u32),
+    /// `amzn2`, `amzn2023`.
+    AmazonLinux(u32),
+    /// `deb12`.
+    Debian(u32),
+    /// The flavour of a Ubuntu kernel, e.g. `generic`, `lowlatency`, `aws`.
+    Ubuntu(String),
+    /// `default` of SUSE.
+    Suse,
+}
+
+define_struct! {
+    /// A kernel release such as `5.15.0-91-generic`, parsed from
+    /// [`Version::uts_release()`](struct.Version.html#method.uts_release) or
+    /// /proc/sys/kernel/osrelease.
+    ///
+    /// Releases are ordered by major, minor and patch, then by the leading
+    /// numbers of extra such as the ABI and build numbers, a `-rcN` release
+    /// sorting before the final one.
+    #[derive(Eq)]
+    pub struct KernelRelease {
+        major: u32,
+        minor: u32,
+        /// 0 if the release has only major and minor, e.g. `6.1-rc1`.
+        patch: u32,
+        /// The suffix after the version number without the leading `-`, e.g. `91-generic`.
+        extra: String,
+    }
+}
+
+impl KernelRelease {
+    pub fn new(major: u32, minor: u32, patch: u32) -> KernelRelease {
+        KernelRelease {
+            major,
+            minor,
+            patch,
+            extra: String::new(),
+        }
+    }
+
+    /// Return true if the release is `major.minor` or later.
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// Detect the distribution from the suffix of the release.
+    pub fn distro(&self) -> Option<Distro> {
+        let number = |s: &str, prefix: &str| -> Option<u32> {
+            let n = s.strip_prefix(prefix)?;
+            let n = n.split('_').next().unwrap_or_default();
+            if n.is_empty() {
+                return None;
+            }
+            n.parse::<u32>().ok()
+        };
+        for token in self.extra.split(['.', '-', '+']) {
+            if let Some(n) = number(token, "el") {
+                return Some(Distro::RedHat(n));
+            }
+            if let Some(n) = number(token, "fc") {
+                return Some(Distro::Fedora(n));
+            }
+            if let Some(n) = number(token, "amzn") {
+                return Some(Distro::AmazonLinux(n));
+            }
+            if let Some(n) = number(token, "deb") {
+                return Some(Distro::Debian(n));
+            }
+        }
+
+        // `91-generic`, the ABI number followed by the flavour.
+        let mut parts = self.extra.splitn(2, '-');
+        let abi = parts.next().unwrap_or_default();
+        let flavour = parts.next().unwrap_or_default();
+        // SUSE uses a dotted ABI, e.g. `150500.55.39-default`.
+        let is_number = |s: &str, dots: bool| {
+            !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit() || (dots && b == b'.'))
+        };
+        match flavour {
+            "default" if is_number(abi, true) => return Some(Distro::Suse),
+            "generic" | "lowlatency" | "aws" | "azure" | "gcp" | "gke" | "oracle" | "kvm"
+            | "raspi"
+                if is_number(abi, false) =>
+            {
+                return Some(Distro::Ubuntu(flavour.to_string()))
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+impl KernelRelease {
+    /// Return whether extra is a release candidate and the leading numbers of extra,
+    /// e.g. `(true, [1])` for `rc1` and `(false, [1062, 1, 1])` for `1062.1.1.el7.x86_64`.
+    fn extra_key(&self) -> (bool, Vec<u64>) {
+        let (rc, rest) = match self.extra.strip_prefix("rc") {
+            Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => (true, rest),
+            _ => (false, self.extra.as_str()),
+        };
+        let mut numbers = vec![];
+        for token in rest.split(['.', '-', '_', '+']) {
+            match token.parse::<u64>() {
+                Ok(n) => numbers.push(n),
+                Err(_) => break,
+            }
+        }
+        (rc, numbers)
+    }
+}
+
+impl Ord for KernelRelease {
+    fn cmp(&self, other: &KernelRelease) -> std::cmp::Ordering {
+        let (rc, numbers) = self.extra_key();
+        let (other_rc, other_numbers) = other.extra_key();
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| other_rc.cmp(&rc))
+            .then_with(|| numbers.cmp(&other_numbers))
+            .then_with(|| self.extra.cmp(&other.extra))
+    }
+}
+
+impl PartialOrd for KernelRelease {
+    fn partial_cmp(&self, other: &KernelRelease) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for KernelRelease {
+    type Err = crate::ProcErr;
+
+    fn from_str(s: &str) -> Result<KernelRelease, crate::ProcErr> {
+        let s = s.trim();
+        let end = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let mut numbers = s[..end].trim_end_matches('.').split('.');
+        let major = numbers.next().unwrap_or_default().parse::<u32>()?;
+        let minor = numbers
+            .next()
+            .ok_or("minor version not found")?
+            .parse::<u32>()?;
+        let patch = match numbers.next() {
+            Some(p) => p.parse::<u32>()?,
+            None => 0,
+        };
+        let extra = s[end..].trim_start_matches('-').to_string();
+        Ok(KernelRelease {
+            major,
+            minor,
+            patch,
+            extra,
+        })
+    }
+}
+
+impl Version {
+    /// Return the parsed uts_release.
+    pub fn release(&self) -> Result<KernelRelease, crate::ProcErr> {
+        self.uts_release.parse()
+    }
+}
+
+/// Return the release of the running kernel, read from /proc/sys/kernel/osrelease.
+pub fn kernel_release() -> Result<KernelRelease, crate::ProcErr> {
+    let content = std::fs::read_to_string("/proc/sys/kernel/osrelease")?;
+    content.trim().parse()
+}
+
+/// Return [`ProcErr::Unsupported`](../../enum.ProcErr.html#variant.Unsupported)
+/// if the running kernel is older than `major.minor`, which is required by `feature`.
+pub fn require_at_least(major: u32, minor: u32, feature: &str) -> Result<(), crate::ProcErr> {
+    let release = kernel_release()?;
+    if release.at_least(major, minor) {
+        Ok(())
+    } else {
+        Err(crate::ProcErr::Unsupported(format!(
+            "{} requires Linux {}.{}, running {}.{}.{}",
+            feature, major, minor, release.major, release.minor, release.patch
+        )))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -95,4 +285,73 @@ mod test {
         };
         assert_eq!(correct, source.parse::<Version>().unwrap())
     }
+
+    #[test]
+    fn test_parse_kernel_release() {
+        let release = "3.10.0-1062.1.1.el7.x86_64"
+            .parse::<KernelRelease>()
+            .unwrap();
+        assert_eq!((3, 10, 0), (release.major, release.minor, release.patch));
+        assert_eq!("1062.1.1.el7.x86_64", release.extra());
+        assert_eq!(Some(Distro::RedHat(7)), release.distro());
+        assert!(release.at_least(3, 10));
+        assert!(!release.at_least(4, 6));
+
+        let release = "5.15.0-91-generic".parse::<KernelRelease>().unwrap();
+        assert_eq!(
+            Some(Distro::Ubuntu("generic".to_string())),
+            release.distro()
+        );
+        let release = "5.15.0-1051-aws".parse::<KernelRelease>().unwrap();
+        assert_eq!(Some(Distro::Ubuntu("aws".to_string())), release.distro());
+        let release = "5.10.205-195.807.amzn2.x86_64"
+            .parse::<KernelRelease>()
+            .unwrap();
+        assert_eq!(Some(Distro::AmazonLinux(2)), release.distro());
+        let release = "4.18.0-513.5.1.el8_9.x86_64"
+            .parse::<KernelRelease>()
+            .unwrap();
+        assert_eq!(Some(Distro::RedHat(8)), release.distro());
+        let release = "5.14.21-150500.55.39-default"
+            .parse::<KernelRelease>()
+            .unwrap();
+        assert_eq!(Some(Distro::Suse), release.distro());
+        let release = "6.4.0-150600.23.25-default"
+            .parse::<KernelRelease>()
+            .unwrap();
+        assert_eq!(Some(Distro::Suse), release.distro());
+        let release = "6.18.44-fc-v139".parse::<KernelRelease>().unwrap();
+        assert_eq!(None, release.distro());
+
+        let release = "6.1-rc1".parse::<KernelRelease>().unwrap();
+        assert_eq!((6, 1, 0), (release.major, release.minor, release.patch));
+        assert_eq!("rc1", release.extra());
+
+        assert!("6.1.0".parse::<KernelRelease>().unwrap() < "6.10.0".parse().unwrap());
+        assert!("5.4.200".parse::<KernelRelease>().unwrap() < "5.10.1".parse().unwrap());
+        assert!(
+            "5.15.0-91-generic".parse::<KernelRelease>().unwrap()
+                < "5.15.0-100-generic".parse().unwrap()
+        );
+        assert!("6.1.0-rc1".parse::<KernelRelease>().unwrap() < "6.1.0".parse().unwrap());
+        assert!("6.1.0-rc2".parse::<KernelRelease>().unwrap() < "6.1.0-1-generic".parse().unwrap());
+        assert!("6.1-rc9".parse::<KernelRelease>().unwrap() < "6.1-rc10".parse().unwrap());
+        assert!(
+            "4.18.0-513.5.1.el8_9.x86_64"
+                .parse::<KernelRelease>()
+                .unwrap()
+                < "4.18.0-513.11.1.el8_9.x86_64".parse().unwrap()
+        );
+        assert!("6.1".parse::<KernelRelease>().is_ok());
+        assert!("linux".parse::<KernelRelease>().is_err());
+    }
+
+    #[test]
+    fn test_require_at_least() {
+        require_at_least(2, 6, "anything").unwrap();
+        match require_at_least(1000, 0, "future") {
+            Err(crate::ProcErr::Unsupported(_)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
 }